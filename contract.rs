@@ -1,177 +1,8381 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use ink_env::Environment;
 use ink_lang as ink;
 
-#[ink::contract]
+/// Chain extension bridging verification results to the runtime identity pallet
+#[ink::chain_extension]
+pub trait IdentityExtension {
+    type ErrorCode = IdentityExtensionError;
+
+    /// Record the verification status of `account` as a judgement in the identity pallet
+    #[ink(extension = 0x4944_0001, returns_result = false)]
+    fn set_judgement(account: <ink_env::DefaultEnvironment as Environment>::AccountId, verified: bool);
+
+    /// Read whether the identity pallet holds a positive judgement for `account`
+    #[ink(extension = 0x4944_0002, returns_result = false)]
+    fn has_judgement(account: <ink_env::DefaultEnvironment as Environment>::AccountId) -> bool;
+
+    /// Send an XCM message carrying `payload` to contract `target` on sibling parachain `para_id`
+    #[ink(extension = 0x4944_0003, returns_result = false)]
+    fn send_xcm(
+        para_id: u32,
+        target: <ink_env::DefaultEnvironment as Environment>::AccountId,
+        payload: ink_prelude::vec::Vec<u8>,
+    );
+
+    /// Ask the runtime scheduler to call `poke_expiry` for `account` at timestamp `at`
+    #[ink(extension = 0x4944_0004, returns_result = false)]
+    fn schedule_expiry(
+        account: <ink_env::DefaultEnvironment as Environment>::AccountId,
+        at: <ink_env::DefaultEnvironment as Environment>::Timestamp,
+    );
+}
+
+/// Errors reported by the identity pallet chain extension
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum IdentityExtensionError {
+    Failed,
+}
+
+impl ink_env::chain_extension::FromStatusCode for IdentityExtensionError {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            _ => Err(Self::Failed),
+        }
+    }
+}
+
+/// Default environment extended with the identity pallet chain extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CustomEnvironment {}
+
+impl Environment for CustomEnvironment {
+    const MAX_EVENT_TOPICS: usize = <ink_env::DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <ink_env::DefaultEnvironment as Environment>::AccountId;
+    type Balance = <ink_env::DefaultEnvironment as Environment>::Balance;
+    type Hash = <ink_env::DefaultEnvironment as Environment>::Hash;
+    type BlockNumber = <ink_env::DefaultEnvironment as Environment>::BlockNumber;
+    type Timestamp = <ink_env::DefaultEnvironment as Environment>::Timestamp;
+
+    type ChainExtension = IdentityExtension;
+}
+
+#[ink::contract(env = crate::CustomEnvironment)]
 mod did_verifier {
-    use ink_storage::traits::{SpreadAllocate, PackedLayout, SpreadLayout};
+    use ink_env::call::{build_call, Call, ExecutionInput, Selector};
+    use ink_primitives::Key;
+    use ink_storage::traits::{KeyPtr, SpreadAllocate, PackedLayout, SpreadLayout};
+
+    /// Denominator for parameters expressed in basis points
+    const BASIS_POINTS: Balance = 10_000;
+
+    /// Maximum number of registered verification hooks, bounding the cost of notifications
+    const MAX_HOOKS: u32 = 8;
+
+    /// Maximum number of registered XCM attestation targets
+    const MAX_XCM_TARGETS: u32 = 8;
+
+    /// Maximum number of accounts processed by a single `poke_expiry` call
+    const MAX_POKE_BATCH: usize = 50;
+
+    /// Maximum number of identities reviewed by a single batch verification or rejection call
+    const MAX_REVIEW_BATCH: usize = 50;
+
+    /// Maximum number of entries returned by a paginated query
+    const MAX_PAGE_SIZE: u32 = 100;
+
+    /// Blocks per verification-ledger epoch (about a day of 6-second blocks)
+    const EPOCH_LENGTH: BlockNumber = 14_400;
+
+    /// Maximum number of signed operations applied by a single `relay_batch` call
+    const MAX_RELAY_BATCH: usize = 256;
+
+    /// Maximum number of admin actions retained by the audit log; older entries are dropped first
+    const MAX_AUDIT_LOG: u32 = 1024;
+
+    /// Version of the storage layout written by this code, checked before every upgrade
+    const STORAGE_VERSION: u32 = 3;
+
+    /// Raw storage key of the reentrancy guard, written directly so a re-entering callee sees it mid-call
+    const REENTRANCY_GUARD_KEY: [u8; 32] = *b"didv::reentrancy_guard__________";
+
+    /// Raw storage key holding the logic code hash when this code runs behind the forwarder contract
+    const FORWARDER_LOGIC_KEY: [u8; 32] = *b"didv::forwarder_logic___________";
+
+    /// Root key of the `identities` map, the first storage field of every layout since v1
+    const IDENTITIES_ROOT_KEY: [u8; 32] = [0x00; 32];
+
+    /// Layout version carried by every emitted event, bumped whenever an event's fields change
+    pub const EVENT_SCHEMA_VERSION: u8 = 4;
+
+    /// Length in bytes of a holder name field
+    pub const MAX_NAME_LENGTH: usize = 64;
+    /// Length in bytes of a document id field
+    pub const MAX_DOCUMENT_ID_LENGTH: usize = 32;
+
+    /// Holder name as UTF-8 bytes, zero-padded to `MAX_NAME_LENGTH`
+    pub type Name = [u8; MAX_NAME_LENGTH];
+    /// Document id as UTF-8 bytes, zero-padded to `MAX_DOCUMENT_ID_LENGTH`
+    pub type DocumentId = [u8; MAX_DOCUMENT_ID_LENGTH];
+
+    /// Maximum number of proof entries carried by one identity
+    const MAX_PROOF_ENTRIES: usize = 8;
+
+    /// Maximum number of per-field commitments held for one identity
+    const MAX_FIELD_COMMITMENTS: usize = 16;
+
+    /// Maximum number of accounts linked to one identity, and of operators indexed per holder
+    const MAX_LINKED_ACCOUNTS: usize = 16;
+
+    /// Maximum number of holder keys controlling one identity besides its own account
+    const MAX_HOLDER_KEYS: usize = 8;
+
+    /// Most recent revoked keys listed per identity; older revocations stay on their key records
+    const MAX_REVOKED_KEYS: usize = 32;
+
+    /// Holder reputation points per successful verification and renewal, and for a record without disputes
+    const REPUTATION_PER_VERIFICATION: u32 = 10;
+    const REPUTATION_PER_RENEWAL: u32 = 5;
+    const REPUTATION_CLEAN_RECORD_BONUS: u32 = 20;
+
+    /// Credential type registered at deployment, requiring every attribute and using the configured validity
+    const DEFAULT_CREDENTIAL_TYPE: u32 = 0;
+
+    /// Operator permission to update a pending identity's details
+    pub const PERMISSION_UPDATE: u32 = 1 << 0;
+    /// Operator permission to post and reclaim verification bounties
+    pub const PERMISSION_BOUNTY: u32 = 1 << 1;
+
+    /// Data categories a holder can consent to share through a read grant
+    pub const DATA_CATEGORY_NAME: u32 = 1 << 0;
+    pub const DATA_CATEGORY_AGE: u32 = 1 << 1;
+    pub const DATA_CATEGORY_DOCUMENT: u32 = 1 << 2;
+    pub const DATA_CATEGORY_ALL: u32 = DATA_CATEGORY_NAME | DATA_CATEGORY_AGE | DATA_CATEGORY_DOCUMENT;
+
+    /// Verifier specializations, required by the document rules of a `DocumentKind`
+    pub const SPECIALIZATION_PASSPORT: u32 = 1 << 0;
+    pub const SPECIALIZATION_NATIONAL_ID: u32 = 1 << 1;
+    pub const SPECIALIZATION_DRIVER_LICENSE: u32 = 1 << 2;
+    pub const SPECIALIZATION_RESIDENCE_PERMIT: u32 = 1 << 3;
+    /// Verifier specialization required to attest liveness checks
+    pub const SPECIALIZATION_BIOMETRIC: u32 = 1 << 4;
+
+    /// Lifecycle notifications a holder can opt out of; a cleared flag suppresses the event carrying their account
+    pub const NOTIFY_EXPIRY: u32 = 1 << 0;
+    pub const NOTIFY_REVERIFICATION: u32 = 1 << 1;
+    pub const NOTIFY_RENEWAL: u32 = 1 << 2;
+    pub const NOTIFY_PURGE: u32 = 1 << 3;
+    pub const NOTIFY_ALL: u32 = NOTIFY_EXPIRY | NOTIFY_REVERIFICATION | NOTIFY_RENEWAL | NOTIFY_PURGE;
+
+    /// Message groups the owner can disable individually through `set_disabled_messages`
+    pub const MESSAGE_SUBMIT: u32 = 1 << 0; // Every submission path, signed, permit-paid and relayed included
+    pub const MESSAGE_UPDATE: u32 = 1 << 1;
+    pub const MESSAGE_VERIFY: u32 = 1 << 2;
+    pub const MESSAGE_REJECT: u32 = 1 << 3; // Rejections and change requests
+    pub const MESSAGE_RENEWAL: u32 = 1 << 4;
+    pub const MESSAGE_EVIDENCE: u32 = 1 << 5; // Proof entries, field commitments and biometric templates
+    pub const MESSAGE_ALL: u32 =
+        MESSAGE_SUBMIT | MESSAGE_UPDATE | MESSAGE_VERIFY | MESSAGE_REJECT | MESSAGE_RENEWAL | MESSAGE_EVIDENCE;
+
+    /// Purpose code of the consent given by accepting the terms of service (identity verification)
+    pub const CONSENT_PURPOSE_VERIFICATION: u32 = 0;
+
+    /// Version of the typed-data signing scheme, part of the domain separator
+    const SIGNING_VERSION: u32 = 1;
+
+    /// Type descriptors hashed into the domain separator and typed struct hashes
+    const DOMAIN_TYPE: &[u8] = b"DidVerifierDomain(u32 chain_id,AccountId contract,u32 version)";
+    const SUBMISSION_TYPE: &[u8] =
+        b"SubmissionPayload([u8;64] name,u32 age,[u8;32] document_id,[u8;32] proof_hash,[u8;2] jurisdiction,DocumentKind document_kind,Option<u64> document_expires_at,[u8;32] terms_hash,u32 credential_type,u64 deadline,u64 nonce)";
+    const UPDATE_TYPE: &[u8] =
+        b"UpdatePayload([u8;64] name,u32 age,[u8;32] document_id,[u8;32] proof_hash,u64 deadline,u64 nonce)";
+    const GUARDIAN_REVOCATION_TYPE: &[u8] = b"GuardianRevocation(AccountId account,u64 deadline,u64 nonce)";
+    const HOLDER_KEY_ADDITION_TYPE: &[u8] =
+        b"HolderKeyAddition(AccountId identity,AccountId key,u64 deadline,u64 nonce)";
+    const VERIFICATION_TYPE: &[u8] =
+        b"VerificationPayload(AccountId account,[u8;32] proof_hash,AccountId verifier,u64 deadline,u64 nonce)";
+
+    /// Selector of `OnVerificationChanged::on_verification_changed`
+    const ON_VERIFICATION_CHANGED_SELECTOR: [u8; 4] =
+        ink_lang::selector_bytes!("OnVerificationChanged::on_verification_changed");
+
+    /// Selectors of the PSP22 fee token messages the contract calls
+    const PSP22_TRANSFER_SELECTOR: [u8; 4] = ink_lang::selector_bytes!("PSP22::transfer");
+    const PSP22_TRANSFER_FROM_SELECTOR: [u8; 4] = ink_lang::selector_bytes!("PSP22::transfer_from");
+    const PSP22_PERMIT_SELECTOR: [u8; 4] = ink_lang::selector_bytes!("PSP22Permit::permit");
+
+    /// Selector of `receive_migrated_record` on the deployment records are migrated to
+    const RECEIVE_MIGRATED_RECORD_SELECTOR: [u8; 4] = ink_lang::selector_bytes!("receive_migrated_record");
+
+    /// Verification status of a submitted identity
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Status {
+        Pending,
+        Verified,
+        Rejected,
+        Revoked,
+        Expired,
+        ChangesRequested, // Returned to the holder for corrections
+        Approved,         // Approved by a verifier, open to contest until the challenge period ends
+        PendingReverification, // Verified details changed, awaiting a fresh verification
+        Suspended,        // Verification suspended while the account or its document is denylisted
+        Withdrawn,        // Submission cancelled by the holder before a decision
+    }
+
+    impl Status {
+        /// Whether the identity is waiting for a verifier's decision
+        fn is_pending(self) -> bool {
+            matches!(self, Status::Pending | Status::PendingReverification)
+        }
+    }
+
+    /// Kind of evidence a proof entry commits to
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ProofKind {
+        Document, // The identity document; always the first entry
+        Liveness, // Selfie or liveness check
+        Address,  // Proof of address
+        Other,
+    }
+
+    /// Hash function used to compute a proof entry's digest
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ProofAlgorithm {
+        Blake2x256,
+        Sha2x256,
+        Keccak256,
+    }
+
+    /// One piece of off-chain evidence committed to by an identity
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ProofEntry {
+        kind: ProofKind,
+        algo: ProofAlgorithm,
+        digest: [u8; 32],
+        added_at: Timestamp,
+        attested_by: Option<AccountId>, // Verifier that checked the evidence, if any
+    }
+
+    /// Tier of KYC assurance attested for a verified identity, in increasing order
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum KycLevel {
+        Basic,     // Level 1: granted by the verification itself
+        Document,  // Level 2: identity document checked
+        Biometric, // Level 3: biometric match checked
+    }
+
+    /// Kind of identity document a submission is backed by
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum DocumentKind {
+        Passport,
+        NationalId,
+        DriverLicense,
+        ResidencePermit,
+        Other,
+    }
+
+    /// Owner-configured rules for one `DocumentKind`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct DocumentRules {
+        validity: Timestamp,           // Longest verification backed by the kind (0 for no cap)
+        allowed_specializations: u32,  // `SPECIALIZATION_*` bits of which a verifier needs one (0 for any verifier)
+        requires_expiry: bool,         // Whether submissions must state the document's expiry date
+    }
+
+    /// Validity of an underlying document as reported by the document oracle
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum DocumentStatus {
+        Valid,
+        Invalid,
+    }
+
+    /// Identity struct to store user information
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Identity {
+        name: Name,
+        age: u32,
+        document_id: DocumentId,
+        document_hash: [u8; 32], // Blake2x256 hash of `document_id` without its padding
+        proofs: Vec<ProofEntry>, // Up to `MAX_PROOF_ENTRIES` proofs, the document proof first
+        status: Status,
+        verifier: Option<AccountId>, // Optional verifier address
+        fee_paid: Balance,           // Submission fee held until the submission is resolved
+        deposit: Balance,            // Anti-Sybil deposit held until the submission is resolved
+        relayer: Option<AccountId>,  // Relayer that submitted on the holder's behalf, if any
+        verified_at: Timestamp,      // Block timestamp of the verification (0 while unverified)
+        expires_at: Option<Timestamp>, // Deadline after which the verification lapses
+        credential_type: u32,        // Kind of credential the submission applies for
+        attempt_count: u32,          // Number of submissions made by the holder so far
+        reject_reason: Option<RejectReason>, // Why the latest submission was rejected, if it was
+        failed_attempts: u32,        // Rejections and change requests since the owner last cleared the account
+        assigned_verifier: Option<AccountId>, // Only verifier allowed to act on the submission, if the holder chose one
+        assignment_deadline: Option<Timestamp>, // Time after which the targeted request lapses
+        assigned_at: Option<Timestamp>, // Time the current verifier claimed the request
+        submitted_at: Timestamp,     // Block timestamp of the latest submission
+        review_time: Timestamp,      // Time from submission to the verifier's decision (0 while undecided)
+        priority_fee: Balance,       // Expedite fee held until a verifier decides, then paid to that verifier
+        notes_hash: Option<[u8; 32]>, // Hash of the deciding verifier's review notes / evidence bundle
+        certificate_id: Option<u64>, // Certificate issued by the latest successful attestation
+        erased_at: Option<Timestamp>, // Time the personal details were replaced by a tombstone, if they were
+        terms_version: u32,          // Version of the terms of service last accepted (0 if none were published)
+        terms_accepted_at: Timestamp, // Time the holder last accepted the terms
+        jurisdiction: [u8; 2],       // ISO 3166-1 alpha-2 code of the country issuing the document
+        status_changed_at: Timestamp, // Block timestamp of the latest recorded transition, the start of the retention window
+        document_kind: DocumentKind, // Kind of document backing the identity
+        document_expires_at: Option<Timestamp>, // Expiry date of the document, if it has one
+    }
+
+    /// Holder commitment to a single attribute, keyed by the hash of the attribute's name
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct FieldCommitment {
+        commitment: [u8; 32],           // Blake2x256 hash of the attribute value followed by a holder-chosen salt
+        committed_at: Timestamp,
+        verified_by: Option<AccountId>, // Verifier that checked the current commitment, if any
+    }
+
+    /// Biometric template commitment and liveness attestation, kept apart from the identity for stricter reads
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct BiometricRecord {
+        template_hash: [u8; 32],                 // Hash of the holder's biometric template
+        liveness_hash: Option<[u8; 32]>,         // Hash of the liveness-check evidence, once attested
+        liveness_attested_by: Option<AccountId>, // Verifier with the Biometric specialization that ran the check
+        liveness_attested_at: Timestamp,
+    }
+
+    impl Identity {
+        /// When the verification lapses: at its own expiry or the document's, whichever comes first
+        fn lapses_at(&self) -> Option<Timestamp> {
+            match (self.expires_at, self.document_expires_at) {
+                (Some(expires_at), Some(document_expires_at)) => Some(expires_at.min(document_expires_at)),
+                (expires_at, document_expires_at) => expires_at.or(document_expires_at),
+            }
+        }
+
+        /// Digest of the document proof, the one submissions, renewals and verifications refer to
+        fn proof_hash(&self) -> [u8; 32] {
+            self.proofs.first().map_or([0; 32], |entry| entry.digest)
+        }
+
+        /// Replace the document proof, dropping its attestation if the digest changes
+        fn set_proof_hash(&mut self, digest: [u8; 32], added_at: Timestamp) {
+            if self.proof_hash() == digest {
+                return;
+            }
+            let entry = ProofEntry {
+                kind: ProofKind::Document,
+                algo: ProofAlgorithm::Blake2x256,
+                digest,
+                added_at,
+                attested_by: None,
+            };
+            match self.proofs.first_mut() {
+                Some(first) => *first = entry,
+                None => self.proofs.push(entry),
+            }
+        }
+    }
+
+    /// Identity record of the original (v1) layout, with a verified flag instead of a status
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct IdentityV1 {
+        name: String,
+        age: u32,
+        document_id: String,
+        proof_hash: [u8; 32],
+        is_verified: bool,
+        verifier: Option<AccountId>,
+    }
+
+    /// Identity record of storage version 1, with `String` name and document id, read only to migrate it
+    #[derive(scale::Decode)]
+    struct IdentityLayout1 {
+        name: String,
+        age: u32,
+        document_id: String,
+        document_hash: [u8; 32],
+        proof_hash: [u8; 32],
+        status: Status,
+        verifier: Option<AccountId>,
+        fee_paid: Balance,
+        deposit: Balance,
+        relayer: Option<AccountId>,
+        verified_at: Timestamp,
+        expires_at: Option<Timestamp>,
+        credential_type: u32,
+        attempt_count: u32,
+        reject_reason: Option<RejectReason>,
+        failed_attempts: u32,
+        assigned_verifier: Option<AccountId>,
+        assignment_deadline: Option<Timestamp>,
+        assigned_at: Option<Timestamp>,
+        submitted_at: Timestamp,
+        review_time: Timestamp,
+        priority_fee: Balance,
+        notes_hash: Option<[u8; 32]>,
+        certificate_id: Option<u64>,
+        erased_at: Option<Timestamp>,
+        terms_version: u32,
+        terms_accepted_at: Timestamp,
+        jurisdiction: [u8; 2],
+        status_changed_at: Timestamp,
+    }
+
+    impl From<IdentityLayout1> for Identity {
+        fn from(old: IdentityLayout1) -> Self {
+            let mut identity = Identity {
+                name: to_fixed_bytes_lossy(&old.name),
+                age: old.age,
+                document_id: to_fixed_bytes_lossy(&old.document_id),
+                document_hash: old.document_hash,
+                proofs: Vec::new(),
+                status: old.status,
+                verifier: old.verifier,
+                fee_paid: old.fee_paid,
+                deposit: old.deposit,
+                relayer: old.relayer,
+                verified_at: old.verified_at,
+                expires_at: old.expires_at,
+                credential_type: old.credential_type,
+                attempt_count: old.attempt_count,
+                reject_reason: old.reject_reason,
+                failed_attempts: old.failed_attempts,
+                assigned_verifier: old.assigned_verifier,
+                assignment_deadline: old.assignment_deadline,
+                assigned_at: old.assigned_at,
+                submitted_at: old.submitted_at,
+                review_time: old.review_time,
+                priority_fee: old.priority_fee,
+                notes_hash: old.notes_hash,
+                certificate_id: old.certificate_id,
+                erased_at: old.erased_at,
+                terms_version: old.terms_version,
+                terms_accepted_at: old.terms_accepted_at,
+                jurisdiction: old.jurisdiction,
+                status_changed_at: old.status_changed_at,
+                document_kind: DocumentKind::Other,
+                document_expires_at: None,
+            };
+            identity.set_proof_hash(old.proof_hash, old.submitted_at);
+            if let (Status::Verified, Some(entry)) = (old.status, identity.proofs.first_mut()) {
+                entry.attested_by = old.verifier;
+            }
+            identity
+        }
+    }
+
+    /// Identity record of storage version 2, with a single proof hash, read only to migrate it
+    ///
+    /// Version 2 records were written both without and with the trailing document kind and expiry.
+    #[derive(scale::Decode)]
+    struct IdentityLayout2 {
+        name: Name,
+        age: u32,
+        document_id: DocumentId,
+        document_hash: [u8; 32],
+        proof_hash: [u8; 32],
+        status: Status,
+        verifier: Option<AccountId>,
+        fee_paid: Balance,
+        deposit: Balance,
+        relayer: Option<AccountId>,
+        verified_at: Timestamp,
+        expires_at: Option<Timestamp>,
+        credential_type: u32,
+        attempt_count: u32,
+        reject_reason: Option<RejectReason>,
+        failed_attempts: u32,
+        assigned_verifier: Option<AccountId>,
+        assignment_deadline: Option<Timestamp>,
+        assigned_at: Option<Timestamp>,
+        submitted_at: Timestamp,
+        review_time: Timestamp,
+        priority_fee: Balance,
+        notes_hash: Option<[u8; 32]>,
+        certificate_id: Option<u64>,
+        erased_at: Option<Timestamp>,
+        terms_version: u32,
+        terms_accepted_at: Timestamp,
+        jurisdiction: [u8; 2],
+        status_changed_at: Timestamp,
+    }
+
+    impl IdentityLayout2 {
+        /// Convert to the current layout, attaching the document fields stored after the record
+        fn into_identity(self, document_kind: DocumentKind, document_expires_at: Option<Timestamp>) -> Identity {
+            let mut identity = Identity {
+                name: self.name,
+                age: self.age,
+                document_id: self.document_id,
+                document_hash: self.document_hash,
+                proofs: Vec::new(),
+                status: self.status,
+                verifier: self.verifier,
+                fee_paid: self.fee_paid,
+                deposit: self.deposit,
+                relayer: self.relayer,
+                verified_at: self.verified_at,
+                expires_at: self.expires_at,
+                credential_type: self.credential_type,
+                attempt_count: self.attempt_count,
+                reject_reason: self.reject_reason,
+                failed_attempts: self.failed_attempts,
+                assigned_verifier: self.assigned_verifier,
+                assignment_deadline: self.assignment_deadline,
+                assigned_at: self.assigned_at,
+                submitted_at: self.submitted_at,
+                review_time: self.review_time,
+                priority_fee: self.priority_fee,
+                notes_hash: self.notes_hash,
+                certificate_id: self.certificate_id,
+                erased_at: self.erased_at,
+                terms_version: self.terms_version,
+                terms_accepted_at: self.terms_accepted_at,
+                jurisdiction: self.jurisdiction,
+                status_changed_at: self.status_changed_at,
+                document_kind,
+                document_expires_at,
+            };
+            identity.set_proof_hash(self.proof_hash, self.submitted_at);
+            if let (Status::Verified, Some(entry)) = (self.status, identity.proofs.first_mut()) {
+                entry.attested_by = self.verifier;
+            }
+            identity
+        }
+    }
+
+    /// A storage cell's bytes, read verbatim so they can be matched against each known layout
+    struct RawCell(Vec<u8>);
+
+    impl scale::Decode for RawCell {
+        fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+            let len = input.remaining_len()?.ok_or("Unknown cell length")?;
+            let mut bytes = Vec::new();
+            bytes.resize(len, 0);
+            input.read(&mut bytes)?;
+            Ok(RawCell(bytes))
+        }
+    }
+
+    /// Operation priced by the fee schedule
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum FeeKind {
+        Submission,   // Every submission path, before the congestion multiplier
+        Renewal,      // `start_renewal`, once the holder's free renewals are used up
+        Expedite,     // Moving a pending submission into the priority lane
+        Presentation, // Recording a signed attestation with `sign_attestation`
+    }
+
+    /// Fee of each priced operation, tunable by the owner through `set_fee_schedule`
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct FeeSchedule {
+        submission: Balance,   // Minimum value that must accompany `submit_identity`
+        renewal: Balance,      // Fee of a renewal past the free tier
+        expedite: Balance,     // Extra fee that moves a pending submission into the priority lane (0 disables it)
+        presentation: Balance, // Fee of recording a signed attestation
+        free_renewals: u32,    // Renewals each holder completes before the renewal fee applies
+    }
+
+    impl FeeSchedule {
+        /// Configured fee of an operation
+        fn fee(&self, kind: FeeKind) -> Balance {
+            match kind {
+                FeeKind::Submission => self.submission,
+                FeeKind::Renewal => self.renewal,
+                FeeKind::Expedite => self.expedite,
+                FeeKind::Presentation => self.presentation,
+            }
+        }
+    }
+
+    /// Owner-configurable contract parameters
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Config {
+        submission_fee: Balance, // Unused since fees moved to the `fees` schedule; kept so later fields keep their cells
+        bounty_timeout: Timestamp, // Time after which an unclaimed bounty can be reclaimed by the holder
+        rejection_cut_bps: u32,    // Share of the submission fee kept as a processing cut on rejection
+        submission_deposit: Balance, // Refundable deposit required on top of the submission fee
+        subscription_price: Balance,   // Price of one relying-party subscription period
+        subscription_period: Timestamp, // Length of one relying-party subscription period
+        verifier_share_bps: u32,       // Share of a verified submission's fee earned by the attesting verifier
+        relayer_reimbursement: Balance, // Paid from the sponsorship pool per verified relayed submission
+        congestion_threshold: u32,     // Pending submissions tolerated before the fee starts to rise
+        congestion_step_bps: u32,      // Fee increase per pending submission above the threshold
+        max_fee_multiplier_bps: u32,   // Upper bound of the congestion multiplier (0 for no bound)
+        hook_gas_limit: u64,           // Gas forwarded to each verification hook notification
+        mirror_to_runtime: bool,       // Mirror verification results into the runtime identity pallet
+        require_valid_document: bool,  // Only verify identities whose document the oracle reported as valid
+        attestation_validity: Timestamp, // Lifetime of a signed attestation statement
+        verification_validity: Timestamp, // Lifetime of a verification (0 for no expiry)
+        keeper_reward: Balance,          // Paid from the treasury per verification expired by `poke_expiry`
+        schedule_expiry: bool,           // Schedule expiry with the runtime scheduler via the chain extension
+        chain_id: u32,                   // Identifier of this chain, part of the signing domain separator
+        fee_token: Option<AccountId>,    // PSP22 token accepted for permit-paid submissions
+        token_submission_fee: Balance,   // Submission fee in the fee token
+        max_failed_attempts: u32,        // Rejections and change requests before the account is locked (0 for no limit)
+        dispute_bond: Balance,           // Minimum bond posted to appeal a rejection or challenge a verification
+        renewal_window: Timestamp,       // How long before expiry a holder may start renewal (0 disables renewals)
+        challenge_period: BlockNumber,   // Blocks an approval stays open to contest before it takes effect (0 for immediate)
+        minor_age_threshold: u32,        // Age of majority where no per-jurisdiction age is set; minors need two sign-offs (0 disables)
+        assignment_timeout: Timestamp,   // How long a targeted verifier has to act before the request lapses (0 for no deadline)
+        priority_fee: Balance,           // Unused since fees moved to the `fees` schedule; kept so later fields keep their cells
+        verification_sla: Timestamp,     // Time a verifier has to decide a claimed request before being penalized (0 disables)
+        auto_assign: bool,               // Assign submissions without a preferred verifier to the least loaded verifier
+        erasure_deadline: Timestamp,     // Time the owner has to process an erasure request before the holder may self-erase
+        rejected_retention: Timestamp,   // Time a rejected record is kept before it may be purged (0 keeps it forever)
+        revoked_retention: Timestamp,    // Time a revoked record is kept before it may be purged (0 keeps it forever)
+        expired_retention: Timestamp,    // Time an expired record is kept before it may be purged (0 keeps it forever)
+        rate_limit_window: Timestamp,    // Length of the per-account submission rate-limit window
+        max_submissions_per_window: u32, // Submissions one account may make per window (0 for no limit)
+        max_submissions_per_block: u32,  // Submissions accepted across all accounts per block (0 for no limit)
+        max_pending: u32,                // Pending submissions accepted before new ones must pay the priority fee (0 for no cap)
+        block_contract_submitters: bool, // Reject submissions paid for or made directly by contract accounts
+        upgrade_delay: Timestamp,        // Time a scheduled code upgrade must wait before it can be applied
+    }
+
+    /// Sibling-parachain contract receiving broadcast attestations
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct XcmTarget {
+        para_id: u32,
+        contract: AccountId,
+    }
+
+    /// Compact attestation sent to sibling parachains
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct XcmAttestation {
+        account: AccountId,
+        status: Status,
+        verifier: Option<AccountId>,
+        block_number: BlockNumber, // Block at which the attestation was produced
+    }
+
+    /// ABI-encoded attestation for EVM bridge contracts with its Keccak-256 digest
+    ///
+    /// The payload is `abi.encode(bytes32 account, bool verified, bytes32 proofHash, address verifier, uint256 blockNumber)`,
+    /// where `verifier` is the Ethereum address of the verifier's registered ECDSA key.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EvmAttestation {
+        payload: Vec<u8>,
+        digest: [u8; 32],
+    }
+
+    /// Statement of verification status covered by a contract attestation signature
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct AttestationStatement {
+        contract: AccountId, // This contract, binding the statement to one deployment
+        account: AccountId,
+        status: Status,
+        issued_at: Timestamp,
+        expires_at: Timestamp,
+    }
+
+    /// Attestation statement signed with the contract-managed attestation key
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SignedAttestation {
+        statement: AttestationStatement,
+        signature: [u8; 65], // ECDSA signature over the Blake2x256 hash of the encoded statement
+    }
+
+    /// Submission signed by the holder and relayed by another account
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SubmissionPayload {
+        name: Name,
+        age: u32,
+        document_id: DocumentId,
+        proof_hash: [u8; 32],
+        jurisdiction: [u8; 2], // ISO 3166-1 alpha-2 code of the issuing country
+        document_kind: DocumentKind,
+        document_expires_at: Option<Timestamp>, // Expiry date of the document, if it has one
+        terms_hash: [u8; 32], // Hash of the terms of service the holder accepts
+        credential_type: u32, // Registered credential type applied for
+        deadline: Timestamp, // Latest block timestamp at which the payload may be applied
+        nonce: u64,          // Must equal the signer's current nonce
+    }
+
+    /// Update of a pending identity signed by the holder
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct UpdatePayload {
+        name: Name,
+        age: u32,
+        document_id: DocumentId,
+        proof_hash: [u8; 32],
+        deadline: Timestamp, // Latest block timestamp at which the payload may be applied
+        nonce: u64,          // Must equal the signer's current nonce
+    }
+
+    /// Independently-signed holder operation relayed in a batch
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum SignedOp {
+        /// SCALE-encoded `SubmissionPayload` and the holder's signature
+        Submit { payload: Vec<u8>, signature: [u8; 65] },
+        /// SCALE-encoded `UpdatePayload` and the holder's signature
+        Update { payload: Vec<u8>, signature: [u8; 65] },
+    }
+
+    /// Hashes shared by every holder-signed payload, computed once per call
+    struct SigningContext {
+        domain_separator: [u8; 32],
+        submission_type_hash: [u8; 32],
+        update_type_hash: [u8; 32],
+    }
+
+    /// Signed operation whose signature has been checked and signer recovered
+    enum VerifiedOp {
+        Submit(AccountId, SubmissionPayload),
+        Update(AccountId, UpdatePayload),
+    }
+
+    /// Verification signed off-chain by a verifier and submitted by any account
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct VerificationPayload {
+        account: AccountId,
+        proof_hash: [u8; 32],
+        verifier: AccountId,
+        deadline: Timestamp, // Latest block timestamp at which the attestation may be applied
+        nonce: u64,          // Must equal the verifier's current nonce
+    }
+
+    /// Revocation of an identity pre-authorized by its guardian
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct GuardianRevocation {
+        account: AccountId,
+        deadline: Timestamp, // Latest block timestamp at which the revocation may be applied
+        nonce: u64,          // Must equal the guardian's current nonce
+    }
+
+    /// Lifetime of a holder key on an identity, kept after removal so past signatures can be checked
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct KeyRecord {
+        added_at: Timestamp,
+        removed_at: Option<Timestamp>, // When the key stopped controlling the identity
+        revoked: bool,                 // Revoked as compromised; the key can never be added back
+    }
+
+    /// Addition of a holder key to an identity, signed by one of its existing keys
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct HolderKeyAddition {
+        identity: AccountId,
+        key: AccountId,      // Account of the key being added
+        deadline: Timestamp, // Latest block timestamp at which the addition may be applied
+        nonce: u64,          // Must equal the signing key's current nonce
+    }
+
+    /// Verification record returned to relying parties (redacted for non-subscribers)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct VerificationRecord {
+        status: Status,
+        verifier: Option<AccountId>, // Attesting verifier, withheld when redacted
+        redacted: bool,
+    }
+
+    /// Administrative operation applied by `multicall`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum AdminOp {
+        SetConfig(Config),
+        AddVerifier(AccountId),
+        RemoveVerifier(AccountId),
+        AddTreasurer(AccountId),
+        RemoveTreasurer(AccountId),
+        AddRelayer(AccountId),
+        RemoveRelayer(AccountId),
+        AddOracle(AccountId),
+        RemoveOracle(AccountId),
+        AddRelyingParty(AccountId),
+        RemoveRelyingParty(AccountId),
+        Pause,
+        Unpause,
+    }
+
+    /// Account or document hash on the sanctions denylist
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum DenylistEntry {
+        Account(AccountId),
+        Document([u8; 32]), // Blake2x256 hash of a document id
+    }
+
+    /// Owner or treasurer action recorded in the audit log
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum AdminAction {
+        ConfigChanged,
+        VerifierAdded(AccountId),
+        VerifierRemoved(AccountId),
+        VerifierTierSet(AccountId, KycLevel),
+        TreasurerAdded(AccountId),
+        TreasurerRemoved(AccountId),
+        RelayerAdded(AccountId),
+        RelayerRemoved(AccountId),
+        OracleAdded(AccountId),
+        OracleRemoved(AccountId),
+        RelyingPartyAdded(AccountId),
+        RelyingPartyRemoved(AccountId),
+        HookAdded(AccountId),
+        HookRemoved(AccountId),
+        XcmTargetAdded(u32, AccountId),
+        XcmTargetRemoved(u32, AccountId),
+        AttestationKeySet,
+        Paused,
+        Unpaused,
+        Frozen,
+        Unfrozen,
+        LockoutCleared(AccountId),
+        RecordsImported(u32),
+        RecordsMigrated(u32),
+        IdentityErased(AccountId),
+        TermsPublished(u32),
+        VerifierAccredited(AccountId, [u8; 2]),
+        AccreditationRevoked(AccountId, [u8; 2]),
+        AgeOfMajoritySet([u8; 2], Option<u32>),
+        DocumentRulesSet(DocumentKind),
+        VerifierSpecializationsSet(AccountId, u32),
+        ComplianceOfficerAdded(AccountId),
+        ComplianceOfficerRemoved(AccountId),
+        LegalHoldPlaced(AccountId),
+        LegalHoldLifted(AccountId),
+        Denylisted(DenylistEntry),
+        Delisted(DenylistEntry),
+        UpgradeScheduled([u8; 32]),
+        UpgradeCancelled([u8; 32]),
+        Upgraded([u8; 32]),
+        MigrationStarted(AccountId),
+        MigrationSourceSet(Option<AccountId>),
+        CredentialTypeRegistered(u32),
+        CredentialTypeDeactivated(u32),
+        Withdrawal(AccountId, Balance),      // (recipient, amount)
+        TokenWithdrawal(AccountId, Balance), // (recipient, amount)
+        StorageMigrated(u32),
+        CircuitBreakersSet(u32), // `MESSAGE_*` groups now disabled
+        FeeScheduleSet,
+        AliasPeriodSet(Timestamp),
+    }
+
+    /// Audit log entry of an admin action
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct AuditEntry {
+        index: u32, // Position in the log, counting entries that have since been dropped
+        action: AdminAction,
+        actor: AccountId,
+        block_number: BlockNumber,
+    }
+
+    /// Reason given by a verifier for rejecting an identity
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RejectReason {
+        DocumentIllegible,
+        HashMismatch,
+        Expired,
+        SuspectedFraud,
+        Other([u8; 32]), // Hash of an off-chain explanation
+    }
+
+    /// A recorded status transition of an identity
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct HistoryEntry {
+        status: Status,              // Status the identity moved to
+        actor: AccountId,            // Account that triggered the transition
+        block_number: BlockNumber,   // Block at which the transition happened
+        reason: String,              // Short description of why the transition happened
+    }
+
+    /// An account newly verified or revoked, appended to the verification ledger in epoch order
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct LedgerEntry {
+        epoch: u32,       // Epoch (block number / `EPOCH_LENGTH`) of the transition
+        account: AccountId,
+        status: Status,   // `Verified` or `Revoked`
+    }
+
+    /// A human-readable handle, keyed by the hash of its name, resolving to a verified account until it expires
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Alias {
+        owner: AccountId,
+        expires_at: Timestamp, // After this the alias resolves to nothing and anyone verified may register it
+    }
+
+    /// A page of identity statuses returned by `iterate_identities`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct IdentityPage {
+        records: Vec<(AccountId, IdentityStatus)>,
+        next_cursor: Option<u32>, // Cursor to pass to the next call, `None` once the registry is exhausted
+    }
+
+    /// Canonical on-chain record behind an off-chain verification certificate
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Certificate {
+        account: AccountId,
+        verifier: Option<AccountId>, // Attesting verifier (withheld from untrusted callers)
+        proof_hash: [u8; 32],        // Proof hash the attestation covered
+        issued_at: Timestamp,
+        expires_at: Option<Timestamp>, // Expiry of the attested verification
+    }
+
+    /// Running decision and SLA statistics of a verifier
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct VerifierStats {
+        decisions: u32,              // Verifications and rejections made
+        total_review_time: Timestamp, // Sum of submission-to-decision times, for averaging
+        sla_breaches: u32,           // Claimed requests decided after `verification_sla` or left to time out
+        open_assignments: u32,       // Requests currently assigned to the verifier and awaiting a decision
+    }
+
+    /// Running submission, verification and revocation counts for a credential type
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct CredentialStats {
+        submitted: u64,
+        verified: u64,
+        revoked: u64,
+    }
+
+    /// Detailed verification status returned by `get_status` (verifier withheld for non-subscribers)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct IdentityStatus {
+        status: Status,
+        verifier: Option<AccountId>,
+        verified_at: Timestamp,
+        expires_at: Option<Timestamp>,
+        credential_type: u32,
+        attempt_count: u32,
+        certificate_id: Option<u64>,
+        jurisdiction: [u8; 2],
+    }
+
+    /// Scoped, expiring approval of an operator acting for a holder
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct OperatorApproval {
+        permissions: u32,      // Bitmask of `PERMISSION_*` flags
+        expires_at: Timestamp, // Block timestamp after which the approval lapses
+    }
+
+    /// Holder's consent for another account to read their identity details
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ReadGrant {
+        purpose: u32,          // Purpose code the holder consented to
+        data_categories: u32,  // Bitmask of `DATA_CATEGORY_*` flags the grantee may read
+        expires_at: Timestamp, // Block timestamp after which the grant lapses
+    }
+
+    /// Verification bounty escrowed by a holder
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Bounty {
+        amount: Balance,
+        posted_at: Timestamp, // Block timestamp of the latest deposit
+    }
+
+    /// Renewal of a verified identity awaiting a verifier
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Renewal {
+        proof_hash: [u8; 32],
+        fee_paid: Balance,
+        deposit: Balance,
+        requested_at: Timestamp,
+    }
+
+    /// Value bounds a credential type places on submitted attributes
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct CredentialSchema {
+        min_age: u32,              // Lowest accepted age
+        max_age: u32,              // Highest accepted age (0 for no maximum)
+        max_name_length: u32,      // Longest accepted name in bytes (0 for no limit)
+        max_document_length: u32,  // Longest accepted document id in bytes (0 for no limit)
+        requires_commitment: bool, // Whether the proof hash must commit to off-chain evidence (non-zero)
+    }
+
+    /// Compact mirror of an identity's status, read by the `is_verified` hot path instead of the full record
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct StatusWord {
+        status: Status,
+        expires_at: Timestamp, // When the verification lapses, its own or the document's expiry (0 for never)
+    }
+
+    /// Credential product registered by the owner
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct CredentialType {
+        name_hash: [u8; 32],
+        required_attributes: u32, // `DATA_CATEGORY_*` bits a submission must provide
+        schema: CredentialSchema, // Bounds checked on every submission and update
+        validity: Timestamp,      // Lifetime of a verification (0 for the configured `verification_validity`)
+        active: bool,             // Whether new submissions may apply for the type
+    }
+
+    /// Code upgrade scheduled by the owner, waiting out the timelock
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PendingUpgrade {
+        code_hash: [u8; 32],
+        storage_version: u32,     // Storage layout version the new code declares
+        executable_at: Timestamp, // Earliest time the upgrade can be applied
+    }
+
+    /// Kind of dispute opened against a verifier's decision
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum DisputeKind {
+        Appeal,    // Holder appeals a rejection
+        Challenge, // Third party challenges a verification
+    }
+
+    /// Bonded dispute awaiting adjudication
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Dispute {
+        kind: DisputeKind,
+        disputant: AccountId,
+        bond: Balance,
+        opened_at: Timestamp,
+        verifier: Option<AccountId>, // Verifier whose decision is disputed
+    }
+
+    /// Treasury accounting for funds collected by the contract
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode, PackedLayout, SpreadLayout)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Treasury {
+        total_collected: Balance, // Resolved fees and forfeited funds credited to the treasury
+        total_withdrawn: Balance, // Funds withdrawn from the treasury
+        total_refunded: Balance,  // Fees refunded to holders of rejected submissions
+        token_collected: Balance, // PSP22 fee token amount collected through permits
+        token_withdrawn: Balance, // PSP22 fee token amount withdrawn from the treasury
+    }
+
+    /// Error returned by PSP22 token contracts
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Psp22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
+    }
+
+    /// Holder-signed permit letting the contract pull `value` of the fee token before `deadline`
+    ///
+    /// The signature is forwarded to the token's `PSP22Permit::permit(owner, spender, value, deadline, signature)`.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Permit {
+        value: Balance,
+        deadline: Timestamp,
+        signature: [u8; 65],
+    }
+
+    /// Contract storage, laid out from the root key in field order
+    ///
+    /// New fields are appended, but a spread struct such as `Config` or `Treasury` takes one cell per field, so changing
+    /// its fields moves the cells of every later field. Record encodings are versioned by `STORAGE_VERSION`, and code
+    /// that changes them must migrate existing entries itself. Raw keys (`REENTRANCY_GUARD_KEY`, `FORWARDER_LOGIC_KEY`)
+    /// sit outside the root layout, so the forwarder's logic hash never collides with a contract field.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct DIDVerifier {
+        identities: ink_storage::collections::HashMap<AccountId, Identity>, // Mapping from account to Identity
+        verifiers: ink_storage::collections::HashSet<AccountId>,            // Set of approved verifiers
+        owner: AccountId,                                                  // Contract owner
+        config: Config,                                                    // Owner-configurable parameters
+        treasurers: ink_storage::collections::HashSet<AccountId>,          // Accounts allowed to withdraw treasury funds
+        treasury: Treasury,                                                // Treasury accounting
+        bounties: ink_storage::collections::HashMap<AccountId, Bounty>,    // Escrowed verification bounties per holder
+        subscriptions: ink_storage::collections::HashMap<AccountId, Timestamp>, // Relying-party subscription expiry times
+        earnings: ink_storage::collections::HashMap<AccountId, Balance>,   // Claimable verifier earnings
+        relayers: ink_storage::collections::HashSet<AccountId>,            // Registered relayers allowed to sponsor submissions
+        sponsorship_pool: Balance,                                         // Owner-funded pool reimbursing relayers
+        pending: ink_storage::collections::Vec<AccountId>,                 // Accounts whose submissions await verification
+        pending_positions: ink_storage::collections::HashMap<AccountId, u32>, // Index of each pending account in `pending`
+        priority_pending: ink_storage::collections::Vec<AccountId>,         // Expedited accounts awaiting verification
+        priority_positions: ink_storage::collections::HashMap<AccountId, u32>, // Index of each expedited account in `priority_pending`
+        badges: ink_storage::collections::HashMap<AccountId, u64>,         // Soulbound badge id held by each verified holder
+        badge_owners: ink_storage::collections::HashMap<u64, AccountId>,   // Owner of each live badge id
+        next_badge_id: u64,                                                // Id assigned to the next minted badge
+        hooks: ink_storage::collections::Vec<AccountId>,                   // Contracts notified of verification status changes
+        relying_parties: ink_storage::collections::HashSet<AccountId>,     // Trusted dApps allowed to call detail-level getters
+        xcm_targets: ink_storage::collections::Vec<XcmTarget>,             // Sibling-parachain contracts receiving attestations
+        verifier_keys: ink_storage::collections::HashMap<AccountId, [u8; 33]>, // Compressed ECDSA public key of each verifier
+        oracles: ink_storage::collections::HashSet<AccountId>,             // Document-validity oracles
+        document_index: ink_storage::collections::HashMap<[u8; 32], AccountId>, // Account that submitted each document hash
+        document_statuses: ink_storage::collections::HashMap<[u8; 32], DocumentStatus>, // Oracle-reported document validity
+        attestation_key: Option<[u8; 33]>,                                 // Compressed ECDSA key signing exportable attestations
+        signed_attestations: ink_storage::collections::HashMap<AccountId, SignedAttestation>, // Latest signed attestation per account
+        nonces: ink_storage::collections::HashMap<AccountId, u64>,         // Next nonce expected from each signer
+        operators: ink_storage::collections::HashMap<(AccountId, AccountId), OperatorApproval>, // (holder, operator) approvals
+        guardians: ink_storage::collections::HashMap<AccountId, [u8; 33]>, // Guardian ECDSA key allowed to revoke each identity
+        attestations_by_verifier: ink_storage::collections::HashMap<(AccountId, u32), AccountId>, // (verifier, index) to attested account
+        attestation_counts: ink_storage::collections::HashMap<AccountId, u32>, // Number of attestations made by each verifier
+        credential_stats: ink_storage::collections::HashMap<u32, CredentialStats>, // Lifecycle counts per credential type
+        history: ink_storage::collections::HashMap<(AccountId, u32), HistoryEntry>, // (account, index) to status transition
+        history_counts: ink_storage::collections::HashMap<AccountId, u32>, // Number of recorded transitions per account
+        accounts: ink_storage::collections::Vec<AccountId>, // Every account with a stored identity, in first-submission order
+        paused: bool, // Whether submissions, updates and verifier decisions are halted
+        frozen: bool, // Whether every verified status reads as suspended after a verifier-key compromise
+        locked_accounts: ink_storage::collections::HashSet<AccountId>, // Accounts locked out after too many failed attempts
+        kyc_levels: ink_storage::collections::HashMap<AccountId, KycLevel>, // Highest KYC tier attested for each identity
+        kyc_attestations: ink_storage::collections::HashMap<(AccountId, KycLevel), AccountId>, // Verifier that attested each tier
+        verifier_tiers: ink_storage::collections::HashMap<AccountId, KycLevel>, // Highest tier each verifier may attest (Basic if unset)
+        disputes: ink_storage::collections::HashMap<AccountId, Dispute>, // Open dispute per identity
+        verifier_reputation: ink_storage::collections::HashMap<AccountId, i32>, // Net dispute outcomes less SLA breaches per verifier
+        renewals: ink_storage::collections::HashMap<AccountId, Renewal>, // Pending renewal per verified identity
+        verifier_stats: ink_storage::collections::HashMap<AccountId, VerifierStats>, // Decision and SLA statistics per verifier
+        approvals: ink_storage::collections::HashMap<AccountId, BlockNumber>, // Block at which each open approval can be finalized
+        cosigners: ink_storage::collections::HashMap<AccountId, AccountId>, // Co-signer designated by each holder for minor applications
+        sign_offs: ink_storage::collections::HashMap<AccountId, AccountId>, // First verifier sign-off on each minor's application
+        cosigned: ink_storage::collections::HashSet<AccountId>, // Minor applications signed off by the designated co-signer
+        certificates: ink_storage::collections::HashMap<u64, Certificate>, // Certificate id to the attestation it records
+        next_certificate_id: u64, // Id assigned to the next issued certificate
+        audit_log: ink_storage::collections::HashMap<u32, AuditEntry>, // Retained admin actions by log index
+        audit_count: u32, // Number of admin actions ever recorded
+        erasure_requests: ink_storage::collections::HashMap<AccountId, Timestamp>, // Processing deadline of each open erasure request
+        terms: ink_storage::collections::HashMap<u32, [u8; 32]>, // Hash of each published terms-of-service version
+        terms_version: u32,     // Current terms version (0 until terms are published)
+        min_terms_version: u32, // Oldest terms version holders may still rely on
+        accreditations: ink_storage::collections::HashSet<(AccountId, [u8; 2])>, // (verifier, jurisdiction) pairs a verifier may attest
+        denylist: ink_storage::collections::HashSet<DenylistEntry>, // Sanctioned accounts and document hashes
+        compliance_officers: ink_storage::collections::HashSet<AccountId>, // Accounts allowed to place and lift legal holds
+        legal_holds: ink_storage::collections::HashMap<AccountId, AccountId>, // Held identity to the officer who placed the hold
+        read_grants: ink_storage::collections::HashMap<(AccountId, AccountId), ReadGrant>, // (holder, grantee) read consents
+        ages_of_majority: ink_storage::collections::HashMap<[u8; 2], u32>, // Age of majority per jurisdiction
+        submission_windows: ink_storage::collections::HashMap<AccountId, (Timestamp, u32)>, // Start and count of each account's rate-limit window
+        block_submissions: (BlockNumber, u32), // Submissions accepted in the given block
+        storage_version: u32, // Version of the storage layout currently in use
+        pending_upgrade: Option<PendingUpgrade>, // Code upgrade waiting out the timelock, if any
+        migration_target: Option<AccountId>, // Deployment records are being migrated to, if in migration mode
+        migration_source: Option<AccountId>, // Deployment allowed to push migrated records here, if any
+        credential_types: ink_storage::collections::HashMap<u32, CredentialType>, // Registered credential types by id
+        status_words: ink_storage::collections::HashMap<AccountId, StatusWord>, // Status and expiry of every identity, updated on each transition
+        document_rules: ink_storage::collections::HashMap<DocumentKind, DocumentRules>, // Rules per document kind (defaults if unset)
+        verifier_specializations: ink_storage::collections::HashMap<AccountId, u32>, // `SPECIALIZATION_*` bits held by each verifier
+        field_commitments: ink_storage::collections::HashMap<(AccountId, [u8; 32]), FieldCommitment>, // Per-field commitments by account and field-name hash
+        committed_fields: ink_storage::collections::HashMap<AccountId, Vec<[u8; 32]>>, // Field-name hashes committed by each account
+        biometrics: ink_storage::collections::HashMap<AccountId, BiometricRecord>, // Biometric template and liveness attestation per identity
+        notification_flags: ink_storage::collections::HashMap<AccountId, u32>, // `NOTIFY_*` flags chosen by holders; absent means `NOTIFY_ALL`
+        renewal_counts: ink_storage::collections::HashMap<AccountId, u32>, // Renewals completed per holder, counted against `free_renewals`
+        ledger: ink_storage::collections::HashMap<u32, LedgerEntry>, // Verifications and revocations by sequence number, epochs non-decreasing
+        ledger_len: u32,                                             // Number of ledger entries
+        aliases: ink_storage::collections::HashMap<[u8; 32], Alias>,  // Handle registry keyed by name hash
+        operators_of: ink_storage::collections::HashMap<AccountId, Vec<AccountId>>, // Reverse index of `operators`: operators approved per holder
+        linked_accounts: ink_storage::collections::HashMap<AccountId, Vec<AccountId>>, // Wallets linked to each identity
+        linked_to: ink_storage::collections::HashMap<AccountId, AccountId>, // Identity each linked wallet belongs to
+        pending_links: ink_storage::collections::HashMap<AccountId, AccountId>, // Link offered to a wallet, awaiting its acceptance
+        holder_keys: ink_storage::collections::HashMap<AccountId, Vec<AccountId>>, // Extra keys controlling each identity
+        key_identity: ink_storage::collections::HashMap<AccountId, AccountId>, // Identity each holder key controls
+        key_records: ink_storage::collections::HashMap<(AccountId, AccountId), KeyRecord>, // (identity, key) lifetimes
+        revoked_keys: ink_storage::collections::HashMap<AccountId, Vec<AccountId>>, // Keys revoked per identity
+        verification_counts: ink_storage::collections::HashMap<AccountId, u32>, // Successful verifications per holder
+        dispute_counts: ink_storage::collections::HashMap<AccountId, u32>,      // Challenges upheld against each holder's identity
+        verifier_timeouts: ink_storage::collections::HashMap<AccountId, u32>,   // Claimed requests each verifier let time out
+        alias_of: ink_storage::collections::HashMap<AccountId, [u8; 32]>, // Reverse record: the alias each account owns
+        disabled_messages: u32, // `MESSAGE_*` groups switched off by their circuit breaker
+        fees: FeeSchedule,      // Fee table keyed by `FeeKind`
+        alias_period: Timestamp, // Registration period of an alias, renewable by its owner (0 disables aliases)
+    }
+
+    #[ink(event)]
+    pub struct Paused {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyFrozen {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyUnfrozen {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct UpgradeScheduled {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        code_hash: [u8; 32],
+        storage_version: u32,
+        executable_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct UpgradeCancelled {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        code_hash: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct Upgraded {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        code_hash: [u8; 32],
+        storage_version: u32,
+    }
+
+    #[ink(event)]
+    pub struct StorageMigrated {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        from: u32,
+        to: u32,
+    }
+
+    #[ink(event)]
+    pub struct MigrationStarted {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        target: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RecordMigrated {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        target: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct MigratedRecordReceived {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        source: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct CredentialTypeRegistered {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        id: u32,
+        name_hash: [u8; 32],
+        required_attributes: u32,
+        validity: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct CredentialTypeDeactivated {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        id: u32,
+    }
+
+    #[ink(event)]
+    pub struct ProofAdded {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        index: u32,
+        kind: ProofKind,
+        digest: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct ProofAttested {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        index: u32,
+        #[ink(topic)]
+        verifier: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct FieldCommitted {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        field: [u8; 32],
+        commitment: [u8; 32],
+        reverification_required: bool, // Whether a verified commitment was replaced
+    }
+
+    #[ink(event)]
+    pub struct RequestCancelled {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        renewal: bool,     // Whether a renewal rather than a submission was cancelled
+        refunded: Balance, // Fee, deposit and bounty returned to the holder
+    }
+
+    #[ink(event)]
+    pub struct BiometricTemplateSet {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct NotificationFlagsSet {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        flags: u32,
+    }
+
+    #[ink(event)]
+    pub struct AliasRegistered {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        #[ink(topic)]
+        owner: AccountId,
+        expires_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct AliasRenewed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        #[ink(topic)]
+        owner: AccountId,
+        expires_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct AliasTransferred {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        name_hash: [u8; 32],
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct LivenessAttested {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct FieldVerified {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        field: [u8; 32],
+        #[ink(topic)]
+        verifier: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct CircuitBreakersChanged {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        by: AccountId,
+        disabled_messages: u32, // `MESSAGE_*` groups now disabled
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct IdentitySubmitted {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        name: Name,
+        age: u32,
+        proof_hash: [u8; 32],
+        #[ink(topic)]
+        document_topic: [u8; 32], // Document hash salted with the contract address
+    }
+
+    #[ink(event)]
+    pub struct IdentityVerified {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        #[ink(topic)]
+        document_topic: [u8; 32], // Document hash salted with the contract address
+    }
+
+    #[ink(event)]
+    pub struct IdentityRejected {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        reason: RejectReason,
+    }
+
+    #[ink(event)]
+    pub struct SubmissionRefunded {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+        processing_cut: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DepositReturned {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DepositSlashed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Subscribed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        subscriber: AccountId,
+        amount: Balance,
+        expires_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct FeeSplit {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        verifier_share: Balance,
+        treasury_share: Balance,
+    }
+
+    #[ink(event)]
+    pub struct EarningsClaimed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        verifier: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RelayedSubmission {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        relayer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SponsorshipPoolFunded {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        amount: Balance,
+        total: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RelayerReimbursed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        relayer: AccountId,
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct IdentityRevoked {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        revoked_by: AccountId,
+    }
+
+    /// PSP34-style transfer event emitted when a soulbound badge is minted or burned
+    #[ink(event)]
+    pub struct Transfer {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: u64,
+    }
+
+    #[ink(event)]
+    pub struct HookNotificationFailed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        hook: AccountId,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RuntimeSyncFailed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AttestationBroadcast {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        para_id: u32,
+        contract: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct VerifierKeySet {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        verifier: AccountId,
+        key: [u8; 33],
+    }
+
+    #[ink(event)]
+    pub struct DocumentStatusReported {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        document_hash: [u8; 32],
+        #[ink(topic)]
+        oracle: AccountId,
+        status: DocumentStatus,
+    }
+
+    #[ink(event)]
+    pub struct AttestationKeySet {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        key: [u8; 33],
+    }
+
+    #[ink(event)]
+    pub struct AttestationSigned {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        status: Status,
+        expires_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct IdentityExpired {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct IdentityPurged {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        status: Status, // Status the record had when its retention window ran out
+    }
+
+    #[ink(event)]
+    pub struct KeeperRewarded {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        keeper: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SignOffRecorded {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        signer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct VerificationApproved {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        finalizes_at: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct ApprovalContested {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        contested_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct LegalHoldPlaced {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        officer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct LegalHoldLifted {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        officer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Denylisted {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        entry: DenylistEntry,
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Delisted {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        entry: DenylistEntry,
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct ConsentReceipt {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        subject: AccountId,
+        #[ink(topic)]
+        recipient: Option<AccountId>, // Account granted read access (`None` for consent to the contract itself)
+        purpose: u32,
+        data_categories: u32, // Bitmask of `DATA_CATEGORY_*` flags covered by the consent
+        terms_hash: [u8; 32], // Terms in force when consent was given (zero while none are published)
+        timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct ConsentWithdrawn {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        subject: AccountId,
+        #[ink(topic)]
+        recipient: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TermsPublished {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        version: u32,
+        terms_hash: [u8; 32],
+        require_reacceptance: bool,
+    }
+
+    #[ink(event)]
+    pub struct TermsAccepted {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        version: u32,
+    }
+
+    #[ink(event)]
+    pub struct ErasureRequested {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        deadline: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct Erased {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        erased_by: AccountId,
+        deadline: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct RenewalRequested {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        proof_hash: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct IdentityRenewed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        expires_at: Option<Timestamp>,
+    }
+
+    #[ink(event)]
+    pub struct AttestationNotesAttached {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        notes_hash: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct DisputeOpened {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        disputant: AccountId,
+        kind: DisputeKind,
+        bond: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DisputeResolved {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        adjudicator: AccountId,
+        kind: DisputeKind,
+        upheld: bool, // Whether the disputant prevailed
+    }
+
+    #[ink(event)]
+    pub struct VerifierAssigned {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        verifier: Option<AccountId>,
+        assigned_by: AccountId,
+        deadline: Option<Timestamp>, // Time after which the targeted request lapses
+    }
+
+    #[ink(event)]
+    pub struct WorkAvailable {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        position: u32,                  // Index in the pending queue when the event was emitted
+        priority_position: Option<u32>, // Index in the priority lane, if expedited
+    }
+
+    #[ink(event)]
+    pub struct WorkClaimed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        deadline: Option<Timestamp>, // Time by which the verifier must act (`None` without an assignment timeout)
+    }
+
+    #[ink(event)]
+    pub struct WorkExpired {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        deadline: Timestamp, // Deadline that lapsed
+        position: u32,       // Index in the pending queue, where the request is open again
+    }
+
+    #[ink(event)]
+    pub struct SlaBreached {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        verifier: AccountId,
+        #[ink(topic)]
+        account: AccountId,
+        elapsed: Timestamp, // Time between the claim and the decision
+    }
+
+    #[ink(event)]
+    pub struct IdentityExpedited {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        paid_by: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AssignmentCancelled {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        bounty_refunded: Balance,
+    }
+
+    #[ink(event)]
+    pub struct VerificationTimedOut {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        deadline: Timestamp, // Response deadline the verifier missed
+    }
+
+    #[ink(event)]
+    pub struct KycLevelAttested {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        level: KycLevel,
+    }
+
+    #[ink(event)]
+    pub struct ChangesRequested {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        reason: RejectReason,
+    }
+
+    #[ink(event)]
+    pub struct AccountLocked {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        failed_attempts: u32,
+    }
+
+    #[ink(event)]
+    pub struct AccountUnlocked {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct CertificateIssued {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        certificate_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct ReverificationRequired {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        updated_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct IdentityUpdated {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        updated_by: AccountId,
+        proof_hash: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct OperatorApproved {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        holder: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        permissions: u32,
+        expires_at: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct OperatorRevoked {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        holder: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct HolderKeyAdded {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        identity: AccountId,
+        #[ink(topic)]
+        key: AccountId,
+        added_by: AccountId, // Existing key that signed the addition
+    }
+
+    #[ink(event)]
+    pub struct HolderKeyRemoved {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        identity: AccountId,
+        #[ink(topic)]
+        key: AccountId,
+        removed_by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct HolderKeyRevoked {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        identity: AccountId,
+        #[ink(topic)]
+        key: AccountId,
+        revoked_by: AccountId,
+        revoked_at: Timestamp, // Signatures by the key from this time on are not valid for the identity
+    }
+
+    #[ink(event)]
+    pub struct AccountLinked {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        identity: AccountId,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AccountUnlinked {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        identity: AccountId,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TokenFeePaid {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        token: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TreasuryTokenWithdrawal {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        to: AccountId,
+        #[ink(topic)]
+        caller: AccountId,
+        token: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct GuardianSet {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        key: Option<[u8; 33]>,
+    }
+
+    #[ink(event)]
+    pub struct GuardianRevoked {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        guardian: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TreasuryWithdrawal {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        to: AccountId,
+        #[ink(topic)]
+        caller: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BountyPosted {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+        total: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BountyPaid {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        verifier: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct BountyReclaimed {
+        schema_version: u8, // Version of the event layout (`EVENT_SCHEMA_VERSION`)
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+    }
+
+    /// Interface for other contracts consuming verification results
+    #[ink::trait_definition]
+    pub trait DidRegistry {
+        /// Check if an identity is verified
+        #[ink(message)]
+        fn is_verified(&self, account: AccountId) -> bool;
+
+        /// Get the verification record for an account
+        #[ink(message)]
+        fn get_verification_record(&self, account: AccountId) -> Option<VerificationRecord>;
+    }
+
+    /// Interface implemented by contracts that want to be notified of verification status changes
+    #[ink::trait_definition]
+    pub trait OnVerificationChanged {
+        /// Called after the verification status of `account` changed to `status`
+        #[ink(message)]
+        fn on_verification_changed(&mut self, account: AccountId, status: Status);
+    }
+
+    impl DIDVerifier {
+        /// Constructor initializes the owner as the contract deployer
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            let caller = Self::env().caller();
+            ink_lang::codegen::initialize_contract(|contract: &mut Self| Self::init(contract, caller))
+        }
+
+        /// Constructor assigning administration to `owner` rather than the deployer (e.g. a factory deploying for a multisig)
+        #[ink(constructor)]
+        pub fn new_with_owner(owner: AccountId) -> Self {
+            ink_lang::codegen::initialize_contract(|contract: &mut Self| Self::init(contract, owner))
+        }
+
+        /// Constructor bootstrapping a working registry with its verifiers and configuration in one transaction
+        #[ink(constructor)]
+        pub fn new_with(initial_verifiers: Vec<AccountId>, config: Config) -> Self {
+            let caller = Self::env().caller();
+            ink_lang::codegen::initialize_contract(|contract: &mut Self| {
+                Self::init(contract, caller);
+                contract.config = config;
+                contract.record_admin_action(caller, AdminAction::ConfigChanged);
+                for verifier in initial_verifiers {
+                    contract.verifiers.insert(verifier);
+                    contract.record_admin_action(caller, AdminAction::VerifierAdded(verifier));
+                }
+            })
+        }
+
+        /// Submit identity for verification (must be accompanied by the current submission fee and deposit)
+        ///
+        /// `jurisdiction` is the ISO 3166-1 alpha-2 code of the issuing country, `document_kind` and
+        /// `document_expires_at` describe the document under the kind's `DocumentRules`, and `terms_hash` must match
+        /// the current terms of service once any are published. `credential_type` must be an active registered type.
+        /// When `preferred_verifier` is set, only that verifier can act on the submission.
+        #[ink(message, payable)]
+        #[allow(clippy::too_many_arguments)]
+        pub fn submit_identity(
+            &mut self,
+            name: Name,
+            age: u32,
+            document_id: DocumentId,
+            proof_hash: [u8; 32],
+            jurisdiction: [u8; 2],
+            document_kind: DocumentKind,
+            document_expires_at: Option<Timestamp>,
+            terms_hash: [u8; 32],
+            credential_type: u32,
+            preferred_verifier: Option<AccountId>,
+        ) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            self.ensure_origin_allowed(caller)?;
+            if let Some(verifier) = preferred_verifier {
+                if !self.verifiers.contains(&verifier) {
+                    return Err("Preferred verifier is not registered");
+                }
+                if !self.accreditations.contains(&(verifier, jurisdiction)) {
+                    return Err("Preferred verifier is not accredited for the jurisdiction");
+                }
+                if !self.is_specialized(verifier, document_kind) {
+                    return Err("Preferred verifier lacks the specialization for the document kind");
+                }
+            }
+            let (fee_paid, deposit) = self.collect_submission_payment()?;
+            self.store_submission(
+                caller,
+                name,
+                age,
+                document_id,
+                proof_hash,
+                jurisdiction,
+                document_kind,
+                document_expires_at,
+                terms_hash,
+                credential_type,
+                fee_paid,
+                deposit,
+                None,
+            )?;
+            if preferred_verifier.is_some() {
+                self.set_assigned_verifier(caller, caller, preferred_verifier);
+            }
+            Ok(())
+        }
+
+        /// Submit an identity signed by the holder, recorded against the signer's account
+        ///
+        /// `payload` is a SCALE-encoded `SubmissionPayload` and `signature` the holder's ECDSA
+        /// signature over its typed-data digest. The caller pays the submission fee and deposit,
+        /// unless it is a registered relayer, in which case the submission is sponsored.
+        #[ink(message, payable)]
+        pub fn submit_identity_with_signature(&mut self, payload: Vec<u8>, signature: [u8; 65]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let (fee_paid, deposit, relayer) = if self.relayers.contains(&caller) {
+                (0, 0, Some(caller))
+            } else {
+                self.ensure_origin_allowed(caller)?;
+                let (fee_paid, deposit) = self.collect_submission_payment()?;
+                (fee_paid, deposit, None)
+            };
+
+            let (signer, submission) = self.recover_submission(&self.signing_context(), &payload, &signature)?;
+            self.apply_signed_submission(caller, signer, submission, fee_paid, deposit, relayer)
+        }
+
+        /// Submit a holder-signed identity, pulling the fee from the holder's PSP22 balance with a signed permit
+        ///
+        /// The permit is applied and the fee pulled in the same call, so the holder needs neither
+        /// native tokens nor a separate approval transaction.
+        #[ink(message)]
+        pub fn submit_identity_with_permit(
+            &mut self,
+            payload: Vec<u8>,
+            signature: [u8; 65],
+            permit: Permit,
+        ) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            self.ensure_origin_allowed(caller)?;
+            let token = self.config.fee_token.ok_or("Fee token not configured")?;
+            let amount = self.config.token_submission_fee;
+            if permit.value < amount {
+                return Err("Permit value below submission fee");
+            }
+
+            let (signer, submission) = self.recover_submission(&self.signing_context(), &payload, &signature)?;
+            self.use_nonce(signer, submission.nonce)?;
+            self.pull_token_fee(token, signer, amount, &permit)?;
+
+            self.store_submission(
+                signer,
+                submission.name,
+                submission.age,
+                submission.document_id,
+                submission.proof_hash,
+                submission.jurisdiction,
+                submission.document_kind,
+                submission.document_expires_at,
+                submission.terms_hash,
+                submission.credential_type,
+                0,
+                0,
+                None,
+            )?;
+            self.env().emit_event(RelayedSubmission {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: signer,
+                relayer: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Apply many independently-signed holder operations, returning a result per item (only registered relayers can call this)
+        ///
+        /// Every operation is sponsored like a relayed `submit_identity_with_signature`. All signatures are checked first against a
+        /// domain separator and type hashes computed once for the batch; a failing item does not affect the others.
+        #[ink(message)]
+        pub fn relay_batch(&mut self, ops: Vec<SignedOp>) -> Result<Vec<Result<(), &'static str>>, &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is a registered relayer
+            if !self.relayers.contains(&caller) {
+                return Err("Only relayers can relay submissions");
+            }
+            if ops.len() > MAX_RELAY_BATCH {
+                return Err("Too many operations");
+            }
+
+            let context = self.signing_context();
+            let verified: Vec<_> = ops
+                .into_iter()
+                .map(|op| match op {
+                    SignedOp::Submit { payload, signature } => self
+                        .recover_submission(&context, &payload, &signature)
+                        .map(|(signer, submission)| VerifiedOp::Submit(signer, submission)),
+                    SignedOp::Update { payload, signature } => self
+                        .recover_update(&context, &payload, &signature)
+                        .map(|(signer, update)| VerifiedOp::Update(signer, update)),
+                })
+                .collect();
+
+            Ok(verified
+                .into_iter()
+                .map(|op| match op? {
+                    VerifiedOp::Submit(signer, submission) => {
+                        self.apply_signed_submission(caller, signer, submission, 0, 0, Some(caller))
+                    }
+                    VerifiedOp::Update(signer, update) => self.apply_signed_update(signer, update),
+                })
+                .collect())
+        }
+
+        /// Update the details of a pending identity (holder or an operator with `PERMISSION_UPDATE`)
+        #[ink(message)]
+        pub fn update_identity(
+            &mut self,
+            account: AccountId,
+            name: Name,
+            age: u32,
+            document_id: DocumentId,
+            proof_hash: [u8; 32],
+        ) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            self.ensure_holder_or_operator(account, caller, PERMISSION_UPDATE)?;
+            self.apply_update(account, caller, name, age, document_id, proof_hash)
+        }
+
+        /// Approve an operator to act for the caller with scoped permissions until `expires_at`
+        #[ink(message)]
+        pub fn approve_operator(&mut self, operator: AccountId, permissions: u32, expires_at: Timestamp) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            if operator == caller {
+                return Err("Cannot approve self as operator");
+            }
+            if expires_at <= self.env().block_timestamp() {
+                return Err("Approval already expired");
+            }
+
+            let mut operators = self.operators_of.get(&caller).cloned().unwrap_or_default();
+            if !operators.contains(&operator) {
+                // Drop lapsed approvals before enforcing the bound
+                let now = self.env().block_timestamp();
+                let operators_map = &mut self.operators;
+                operators.retain(|listed| {
+                    let active = operators_map.get(&(caller, *listed)).map_or(false, |approval| approval.expires_at > now);
+                    if !active {
+                        operators_map.take(&(caller, *listed));
+                    }
+                    active
+                });
+                if operators.len() >= MAX_LINKED_ACCOUNTS {
+                    return Err("Too many operators");
+                }
+                operators.push(operator);
+            }
+            self.operators_of.insert(caller, operators);
+            self.operators.insert((caller, operator), OperatorApproval { permissions, expires_at });
+            self.env().emit_event(OperatorApproved {
+                schema_version: EVENT_SCHEMA_VERSION,
+                holder: caller,
+                operator,
+                permissions,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke an operator approved by the caller
+        #[ink(message)]
+        pub fn revoke_operator(&mut self, operator: AccountId) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            self.operators.take(&(caller, operator)).ok_or("Operator not approved")?;
+            if let Some(operators) = self.operators_of.get_mut(&caller) {
+                operators.retain(|listed| *listed != operator);
+            }
+            self.env().emit_event(OperatorRevoked {
+                schema_version: EVENT_SCHEMA_VERSION,
+                holder: caller,
+                operator,
+            });
+
+            Ok(())
+        }
+
+        /// Offer to link another wallet to the caller's identity; the wallet completes the link with `accept_link`
+        #[ink(message)]
+        pub fn link_account(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            self.ensure_not_paused()?;
+            if account == caller {
+                return Err("Cannot link the identity's own account");
+            }
+            if self.identities.get(&caller).is_none() {
+                return Err("Identity not found");
+            }
+            if self.identities.get(&account).is_some()
+                || self.linked_to.get(&account).is_some()
+                || self.key_identity.get(&account).is_some()
+            {
+                return Err("Account already belongs to an identity");
+            }
+            self.pending_links.insert(account, caller);
+            Ok(())
+        }
+
+        /// Accept a link offered by `identity`, linking the caller's wallet to it
+        #[ink(message)]
+        pub fn accept_link(&mut self, identity: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            self.ensure_not_paused()?;
+            if self.pending_links.get(&caller) != Some(&identity) {
+                return Err("No link offered by the identity");
+            }
+            if self.identities.get(&caller).is_some()
+                || self.linked_to.get(&caller).is_some()
+                || self.key_identity.get(&caller).is_some()
+            {
+                return Err("Account already belongs to an identity");
+            }
+            let mut linked = self.linked_accounts.get(&identity).cloned().unwrap_or_default();
+            if linked.len() >= MAX_LINKED_ACCOUNTS {
+                return Err("Too many linked accounts");
+            }
+
+            self.pending_links.take(&caller);
+            linked.push(caller);
+            self.linked_accounts.insert(identity, linked);
+            self.linked_to.insert(caller, identity);
+            self.env().emit_event(AccountLinked {
+                schema_version: EVENT_SCHEMA_VERSION,
+                identity,
+                account: caller,
+            });
+            Ok(())
+        }
+
+        /// Unlink a wallet from an identity (the identity's account or the linked wallet itself)
+        #[ink(message)]
+        pub fn unlink_account(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let identity = self.linked_to.get(&account).copied().ok_or("Account is not linked")?;
+            if caller != identity && caller != account {
+                return Err("Only the identity or the linked account can unlink it");
+            }
+
+            self.unlink(identity, account);
+            Ok(())
+        }
+
+        /// Add a key that can act for an identity, authorized by an existing key's signature (callable by anyone)
+        ///
+        /// `signature` is an existing key's ECDSA signature over the typed-data digest of a `HolderKeyAddition`,
+        /// so the new device can submit it. Every holder-gated message then accepts the new key as the holder.
+        #[ink(message)]
+        pub fn add_holder_key(
+            &mut self,
+            identity: AccountId,
+            key: AccountId,
+            deadline: Timestamp,
+            nonce: u64,
+            signature: [u8; 65],
+        ) -> Result<(), &'static str> {
+            self.ensure_not_paused()?;
+            self.ensure_before_deadline(deadline)?;
+            if self.identities.get(&identity).is_none() {
+                return Err("Identity not found");
+            }
+            let addition = HolderKeyAddition { identity, key, deadline, nonce };
+            let digest = self.typed_digest(HOLDER_KEY_ADDITION_TYPE, &addition);
+            let signer = self.account_from_public_key(&self.recover_public_key(&digest, &signature)?);
+            if self.holder_of(signer) != Ok(identity) {
+                return Err("Signer is not a key of the identity");
+            }
+            if self.identities.get(&key).is_some()
+                || self.key_identity.get(&key).is_some()
+                || self.linked_to.get(&key).is_some()
+            {
+                return Err("Account already belongs to an identity");
+            }
+            if self.key_records.get(&(identity, key)).map_or(false, |record| record.revoked) {
+                return Err("Key has been revoked");
+            }
+            let mut keys = self.holder_keys.get(&identity).cloned().unwrap_or_default();
+            if keys.len() >= MAX_HOLDER_KEYS {
+                return Err("Too many holder keys");
+            }
+            self.use_nonce(signer, nonce)?;
+
+            keys.push(key);
+            self.holder_keys.insert(identity, keys);
+            self.key_identity.insert(key, identity);
+            let record = KeyRecord {
+                added_at: self.env().block_timestamp(),
+                removed_at: None,
+                revoked: false,
+            };
+            self.key_records.insert((identity, key), record);
+            self.env().emit_event(HolderKeyAdded {
+                schema_version: EVENT_SCHEMA_VERSION,
+                identity,
+                key,
+                added_by: signer,
+            });
+            Ok(())
+        }
+
+        /// Remove a holder key from the caller's identity (any of its active keys)
+        #[ink(message)]
+        pub fn remove_holder_key(&mut self, key: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let identity = self.holder_of(caller)?;
+            if self.key_identity.get(&key) != Some(&identity) {
+                return Err("Key does not control the caller's identity");
+            }
+            self.ensure_other_key_active(identity, key)?;
+
+            self.remove_key(identity, key, caller);
+            Ok(())
+        }
+
+        /// Revoke a compromised key of the caller's identity for good (any of its active keys)
+        ///
+        /// Unlike `remove_holder_key`, the key can never be added back. Its record keeps the revocation time, so
+        /// signatures made before it stay checkable. The identity's own account can be revoked too, as long as
+        /// another active key keeps control of the identity.
+        #[ink(message)]
+        pub fn revoke_holder_key(&mut self, key: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let identity = self.holder_of(caller)?;
+            let revoked_at = self.env().block_timestamp();
+            if key == identity {
+                if self.primary_key_revoked(identity) {
+                    return Err("Key has been revoked");
+                }
+                self.ensure_other_key_active(identity, key)?;
+                // The primary account has controlled the identity since it was created
+                let record = KeyRecord {
+                    added_at: 0,
+                    removed_at: Some(revoked_at),
+                    revoked: true,
+                };
+                self.key_records.insert((identity, identity), record);
+            } else {
+                if self.key_identity.get(&key) != Some(&identity) {
+                    return Err("Key does not control the caller's identity");
+                }
+                self.ensure_other_key_active(identity, key)?;
+                self.remove_key(identity, key, caller);
+                if let Some(record) = self.key_records.get_mut(&(identity, key)) {
+                    record.revoked = true;
+                }
+            }
+            let mut revoked = self.get_revoked_keys(identity);
+            if revoked.len() >= MAX_REVOKED_KEYS {
+                revoked.remove(0);
+            }
+            revoked.push(key);
+            self.revoked_keys.insert(identity, revoked);
+            self.env().emit_event(HolderKeyRevoked {
+                schema_version: EVENT_SCHEMA_VERSION,
+                identity,
+                key,
+                revoked_by: caller,
+                revoked_at,
+            });
+            Ok(())
+        }
+
+        /// Let `grantee` read the given `DATA_CATEGORY_*` details of the caller's identity for `purpose` until `expires_at`
+        #[ink(message)]
+        pub fn grant_read_access(
+            &mut self,
+            grantee: AccountId,
+            purpose: u32,
+            data_categories: u32,
+            expires_at: Timestamp,
+        ) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            if !self.identities.contains_key(&caller) {
+                return Err("Identity not found");
+            }
+            if expires_at <= self.env().block_timestamp() {
+                return Err("Grant already expired");
+            }
+
+            self.read_grants.insert((caller, grantee), ReadGrant {
+                purpose,
+                data_categories,
+                expires_at,
+            });
+            self.emit_consent_receipt(caller, Some(grantee), purpose, data_categories);
+            Ok(())
+        }
+
+        /// Withdraw a read grant given by the caller
+        #[ink(message)]
+        pub fn revoke_read_access(&mut self, grantee: AccountId) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            self.read_grants.take(&(caller, grantee)).ok_or("Read access not granted")?;
+            self.env().emit_event(ConsentWithdrawn {
+                schema_version: EVENT_SCHEMA_VERSION,
+                subject: caller,
+                recipient: grantee,
+            });
+            Ok(())
+        }
+
+        /// Withdraw the caller's in-flight submission or renewal, refunding the held fee, deposit and any bounty in full
+        ///
+        /// A withdrawn submission leaves the queue and its verifier assignment, and the holder may submit again.
+        /// Cancelling a renewal keeps the verification it would have extended.
+        #[ink(message)]
+        pub fn cancel_request(&mut self) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.holder_of(self.env().caller())?;
+
+            if let Some(renewal) = self.renewals.take(&caller) {
+                let refunded = renewal.fee_paid.saturating_add(renewal.deposit);
+                self.treasury.total_refunded = self.treasury.total_refunded.saturating_add(renewal.fee_paid);
+                if refunded > 0 {
+                    self.env().transfer(caller, refunded).map_err(|_| "Transfer failed")?;
+                }
+                self.env().emit_event(RequestCancelled {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account: caller,
+                    renewal: true,
+                    refunded,
+                });
+                return Ok(());
+            }
+
+            // Ensure a submission is still awaiting a decision
+            let identity = self.identities.get_mut(&caller).ok_or("Identity not found")?;
+            if identity.status != Status::Pending && identity.status != Status::ChangesRequested {
+                return Err("No request to cancel");
+            }
+            identity.status = Status::Withdrawn;
+            let fees = identity.fee_paid.saturating_add(identity.priority_fee);
+            let deposit = identity.deposit;
+            identity.fee_paid = 0;
+            identity.deposit = 0;
+            identity.priority_fee = 0;
+            let assigned = identity.assigned_verifier.take();
+            identity.assignment_deadline = None;
+            identity.assigned_at = None;
+            self.remove_pending(caller);
+            self.clear_sign_offs(caller);
+            if let Some(verifier) = assigned {
+                self.adjust_workload(verifier, false);
+            }
+            let bounty = self.bounties.take(&caller).map_or(0, |bounty| bounty.amount);
+            self.record_history(caller, Status::Withdrawn, caller, "Cancelled by holder");
+
+            let refunded = fees.saturating_add(deposit).saturating_add(bounty);
+            self.treasury.total_refunded = self.treasury.total_refunded.saturating_add(fees);
+            if refunded > 0 {
+                self.env().transfer(caller, refunded).map_err(|_| "Transfer failed")?;
+            }
+            self.env().emit_event(RequestCancelled {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: caller,
+                renewal: false,
+                refunded,
+            });
+            Ok(())
+        }
+
+        /// Add a proof entry beyond the document proof, such as a liveness or address proof (holder or an operator with `PERMISSION_UPDATE`)
+        ///
+        /// Returns the index of the new entry, which verifiers pass to `attest_proof`.
+        #[ink(message)]
+        pub fn add_proof(
+            &mut self,
+            account: AccountId,
+            kind: ProofKind,
+            algo: ProofAlgorithm,
+            digest: [u8; 32],
+        ) -> Result<u32, &'static str> {
+            let caller = self.env().caller();
+            self.ensure_holder_or_operator(account, caller, PERMISSION_UPDATE)?;
+            self.ensure_enabled(MESSAGE_EVIDENCE)?;
+            if kind == ProofKind::Document {
+                return Err("The document proof is set by submissions and updates");
+            }
+            let now = self.env().block_timestamp();
+
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            if identity.erased_at.is_some() {
+                return Err("Identity has been erased");
+            }
+            if identity.proofs.len() >= MAX_PROOF_ENTRIES {
+                return Err("Too many proof entries");
+            }
+            identity.proofs.push(ProofEntry {
+                kind,
+                algo,
+                digest,
+                added_at: now,
+                attested_by: None,
+            });
+            let index = (identity.proofs.len() - 1) as u32;
+
+            self.env().emit_event(ProofAdded {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                index,
+                kind,
+                digest,
+            });
+            Ok(index)
+        }
+
+        /// Attest that the evidence behind one proof entry was checked (only verifiers can call this)
+        ///
+        /// `digest` must match the entry, so a verifier cannot attest an entry replaced since it was reviewed.
+        #[ink(message)]
+        pub fn attest_proof(&mut self, account: AccountId, index: u32, digest: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure the caller is a verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can attest proofs");
+            }
+
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            let entry = identity.proofs.get_mut(index as usize).ok_or("Proof entry not found")?;
+            if entry.digest != digest {
+                return Err("Proof hash does not match");
+            }
+            entry.attested_by = Some(caller);
+
+            self.env().emit_event(ProofAttested {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                index,
+                verifier: caller,
+            });
+            Ok(())
+        }
+
+        /// Commit to individual attributes as `(field-name hash, commitment)` pairs (holder or an operator with `PERMISSION_UPDATE`)
+        ///
+        /// Replacing a verified commitment only drops that field's verification, so a changed attribute is
+        /// re-verified on its own rather than with the whole identity.
+        #[ink(message)]
+        pub fn commit_fields(&mut self, account: AccountId, commitments: Vec<([u8; 32], [u8; 32])>) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            self.ensure_holder_or_operator(account, caller, PERMISSION_UPDATE)?;
+            self.ensure_enabled(MESSAGE_EVIDENCE)?;
+            match self.identities.get(&account) {
+                Some(identity) if identity.erased_at.is_some() => return Err("Identity has been erased"),
+                Some(_) => {}
+                None => return Err("Identity not found"),
+            }
+
+            let now = self.env().block_timestamp();
+            let mut fields = self.committed_fields.get(&account).cloned().unwrap_or_default();
+            for (field, commitment) in commitments {
+                let previous = self.field_commitments.get(&(account, field)).copied();
+                if previous.map_or(false, |previous| previous.commitment == commitment) {
+                    continue;
+                }
+                if previous.is_none() {
+                    if fields.len() >= MAX_FIELD_COMMITMENTS {
+                        return Err("Too many field commitments");
+                    }
+                    fields.push(field);
+                }
+                self.field_commitments.insert(
+                    (account, field),
+                    FieldCommitment {
+                        commitment,
+                        committed_at: now,
+                        verified_by: None,
+                    },
+                );
+                self.env().emit_event(FieldCommitted {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    field,
+                    commitment,
+                    reverification_required: previous.map_or(false, |previous| previous.verified_by.is_some()),
+                });
+            }
+            self.committed_fields.insert(account, fields);
+            Ok(())
+        }
+
+        /// Commit to the holder's biometric template (only the holder can set it)
+        ///
+        /// Replacing the template drops its liveness attestation, and with it a Biometric KYC level.
+        #[ink(message)]
+        pub fn set_biometric_template(&mut self, template_hash: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            self.ensure_enabled(MESSAGE_EVIDENCE)?;
+            match self.identities.get(&caller) {
+                Some(identity) if identity.erased_at.is_some() => return Err("Identity has been erased"),
+                Some(_) => {}
+                None => return Err("Identity not found"),
+            }
+            if self.biometrics.get(&caller).map_or(false, |record| record.template_hash == template_hash) {
+                return Err("Biometric template unchanged");
+            }
+
+            self.biometrics.insert(
+                caller,
+                BiometricRecord {
+                    template_hash,
+                    liveness_hash: None,
+                    liveness_attested_by: None,
+                    liveness_attested_at: 0,
+                },
+            );
+            if self.kyc_levels.get(&caller) == Some(&KycLevel::Biometric) {
+                self.kyc_levels.insert(caller, KycLevel::Document);
+            }
+            self.env().emit_event(BiometricTemplateSet {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: caller,
+            });
+            Ok(())
+        }
+
+        /// Choose which lifecycle events are emitted with the caller's account as a topic (`NOTIFY_*` flags)
+        #[ink(message)]
+        pub fn set_notification_flags(&mut self, flags: u32) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            if flags & !NOTIFY_ALL != 0 {
+                return Err("Unknown notification flag");
+            }
+            if self.notification_flags.get(&caller).copied().unwrap_or(NOTIFY_ALL) == flags {
+                return Err("Notification flags unchanged");
+            }
+
+            if flags == NOTIFY_ALL {
+                self.notification_flags.take(&caller);
+            } else {
+                self.notification_flags.insert(caller, flags);
+            }
+            self.env().emit_event(NotificationFlagsSet {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: caller,
+                flags,
+            });
+            Ok(())
+        }
+
+        /// Register a free or expired alias for the caller, who must be verified and own no other alias
+        #[ink(message)]
+        pub fn register_alias(&mut self, name_hash: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            self.ensure_not_paused()?;
+            if self.alias_period == 0 {
+                return Err("Aliases are disabled");
+            }
+            if !self.is_verified(caller) {
+                return Err("Only verified accounts can register aliases");
+            }
+            if self.reverse_resolve(caller).is_some() {
+                return Err("Account already owns an alias");
+            }
+            let now = self.env().block_timestamp();
+            if let Some(alias) = self.aliases.get(&name_hash).copied() {
+                if alias.expires_at > now {
+                    return Err("Alias already registered");
+                }
+                // Release the lapsed registration
+                if self.alias_of.get(&alias.owner) == Some(&name_hash) {
+                    self.alias_of.take(&alias.owner);
+                }
+            }
+
+            let expires_at = now.saturating_add(self.alias_period);
+            self.aliases.insert(name_hash, Alias { owner: caller, expires_at });
+            self.alias_of.insert(caller, name_hash);
+            self.env().emit_event(AliasRegistered {
+                schema_version: EVENT_SCHEMA_VERSION,
+                name_hash,
+                owner: caller,
+                expires_at,
+            });
+            Ok(())
+        }
+
+        /// Extend the caller's unexpired alias by one `alias_period`
+        #[ink(message)]
+        pub fn renew_alias(&mut self, name_hash: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            self.ensure_not_paused()?;
+            if self.alias_period == 0 {
+                return Err("Aliases are disabled");
+            }
+            let now = self.env().block_timestamp();
+            let alias = self.aliases.get(&name_hash).copied().ok_or("Alias not found")?;
+            if alias.owner != caller {
+                return Err("Only the alias owner can renew it");
+            }
+            if alias.expires_at <= now {
+                return Err("Alias expired");
+            }
+
+            let expires_at = alias.expires_at.saturating_add(self.alias_period);
+            self.aliases.insert(name_hash, Alias { owner: caller, expires_at });
+            self.env().emit_event(AliasRenewed {
+                schema_version: EVENT_SCHEMA_VERSION,
+                name_hash,
+                owner: caller,
+                expires_at,
+            });
+            Ok(())
+        }
+
+        /// Transfer the caller's unexpired alias to another verified account that owns no alias, keeping its expiry
+        #[ink(message)]
+        pub fn transfer_alias(&mut self, name_hash: [u8; 32], to: AccountId) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            self.ensure_not_paused()?;
+            let alias = self.aliases.get(&name_hash).copied().ok_or("Alias not found")?;
+            if alias.owner != caller {
+                return Err("Only the alias owner can transfer it");
+            }
+            if alias.expires_at <= self.env().block_timestamp() {
+                return Err("Alias expired");
+            }
+            if !self.is_verified(to) {
+                return Err("Aliases can only be transferred to verified accounts");
+            }
+            if self.reverse_resolve(to).is_some() {
+                return Err("Recipient already owns an alias");
+            }
+
+            self.aliases.insert(name_hash, Alias { owner: to, expires_at: alias.expires_at });
+            self.alias_of.take(&caller);
+            self.alias_of.insert(to, name_hash);
+            self.env().emit_event(AliasTransferred {
+                schema_version: EVENT_SCHEMA_VERSION,
+                name_hash,
+                from: caller,
+                to,
+            });
+            Ok(())
+        }
+
+        /// Attest a liveness check against the holder's biometric template (only verifiers with the Biometric specialization)
+        #[ink(message)]
+        pub fn attest_liveness(&mut self, account: AccountId, template_hash: [u8; 32], liveness_hash: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure the caller is a verifier with the Biometric specialization
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can attest liveness");
+            }
+            if self.verifier_specializations.get(&caller).copied().unwrap_or(0) & SPECIALIZATION_BIOMETRIC == 0 {
+                return Err("Verifier lacks the Biometric specialization");
+            }
+            self.ensure_not_paused()?;
+            let now = self.env().block_timestamp();
+
+            let record = self.biometrics.get_mut(&account).ok_or("Biometric template not set")?;
+            if record.template_hash != template_hash {
+                return Err("Biometric template does not match");
+            }
+            record.liveness_hash = Some(liveness_hash);
+            record.liveness_attested_by = Some(caller);
+            record.liveness_attested_at = now;
+
+            self.env().emit_event(LivenessAttested {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier: caller,
+            });
+            Ok(())
+        }
+
+        /// Attest that the value behind one field commitment was checked (only verifiers can call this)
+        #[ink(message)]
+        pub fn verify_field(&mut self, account: AccountId, field: [u8; 32], commitment: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure the caller is a verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can verify fields");
+            }
+
+            let entry = self.field_commitments.get_mut(&(account, field)).ok_or("Field commitment not found")?;
+            if entry.commitment != commitment {
+                return Err("Commitment does not match");
+            }
+            entry.verified_by = Some(caller);
+
+            self.env().emit_event(FieldVerified {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                field,
+                verifier: caller,
+            });
+            Ok(())
+        }
+
+        /// Verify an identity with a matching proof hash (only verifiers can call this)
+        #[ink(message)]
+        pub fn verify_identity(&mut self, account: AccountId, proof_hash: [u8; 32]) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is a registered verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can verify identities");
+            }
+
+            self.apply_verification(account, caller, proof_hash)
+        }
+
+        /// Designate the account whose sign-off can stand in for a second verifier on the caller's minor application
+        #[ink(message)]
+        pub fn set_cosigner(&mut self, cosigner: Option<AccountId>) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            match cosigner {
+                Some(cosigner) => {
+                    if cosigner == caller {
+                        return Err("Holder cannot co-sign their own application");
+                    }
+                    self.cosigners.insert(caller, cosigner);
+                }
+                None => {
+                    self.cosigners.take(&caller);
+                }
+            }
+            self.cosigned.take(&caller);
+            Ok(())
+        }
+
+        /// Sign off on a minor's pending application (only the holder's designated co-signer)
+        ///
+        /// If a verifier has already signed off, the identity is approved immediately.
+        #[ink(message)]
+        pub fn cosign_identity(&mut self, account: AccountId) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is the holder's designated co-signer
+            if self.cosigners.get(&account) != Some(&caller) {
+                return Err("Only the designated co-signer can sign off");
+            }
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+            if !identity.status.is_pending() {
+                return Err("Identity is not pending verification");
+            }
+            let proof_hash = identity.proof_hash();
+
+            self.cosigned.insert(account);
+            self.env().emit_event(SignOffRecorded {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                signer: caller,
+            });
+            match self.sign_offs.get(&account).copied() {
+                Some(verifier) => self.apply_verification(account, verifier, proof_hash),
+                None => Ok(()),
+            }
+        }
+
+        /// Finalize an approval whose challenge period has ended (callable by anyone)
+        #[ink(message)]
+        pub fn finalize_verification(&mut self, account: AccountId) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            self.ensure_not_paused()?;
+            let finalizes_at = self.approvals.get(&account).copied().ok_or("No approval to finalize")?;
+            if self.env().block_number() < finalizes_at {
+                return Err("Challenge period has not ended");
+            }
+            let verifier = self
+                .identities
+                .get(&account)
+                .and_then(|identity| identity.verifier)
+                .ok_or("Identity not found")?;
+
+            self.approvals.take(&account);
+            self.complete_verification(account, verifier)
+        }
+
+        /// Contest an approval during its challenge period, returning the identity to the verification queue
+        /// (only the owner or a verifier other than the approving one)
+        #[ink(message)]
+        pub fn contest_approval(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let finalizes_at = self.approvals.get(&account).copied().ok_or("No approval to contest")?;
+            if self.env().block_number() >= finalizes_at {
+                return Err("Challenge period has ended");
+            }
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            // Ensure the caller is the owner or an independent verifier
+            if caller != self.owner && (!self.verifiers.contains(&caller) || identity.verifier == Some(caller)) {
+                return Err("Only the owner or another verifier can contest approvals");
+            }
+
+            identity.status = Status::Pending;
+            identity.verifier = None;
+            self.approvals.take(&account);
+            self.enqueue_pending(account, false);
+            self.record_history(account, Status::Pending, caller, "Approval contested");
+            self.env().emit_event(ApprovalContested {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                contested_by: caller,
+            });
+            Ok(())
+        }
+
+        /// Verify several identities in one call, returning a result per item (only verifiers can call this)
+        ///
+        /// An item whose bounty, deposit or relayer reimbursement the contract cannot pay out fails
+        /// before any of its state changes.
+        #[ink(message)]
+        pub fn verify_identities(
+            &mut self,
+            items: Vec<(AccountId, [u8; 32])>,
+        ) -> Result<Vec<Result<(), &'static str>>, &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is a registered verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can verify identities");
+            }
+            if items.len() > MAX_REVIEW_BATCH {
+                return Err("Too many identities");
+            }
+
+            Ok(items
+                .into_iter()
+                .map(|(account, proof_hash)| self.apply_verification(account, caller, proof_hash))
+                .collect())
+        }
+
+        /// Apply a verification signed off-chain by a registered verifier (callable by anyone)
+        ///
+        /// `payload` is a SCALE-encoded `VerificationPayload` and `signature` the verifier's ECDSA
+        /// signature over its typed-data digest, made with the key registered via `set_verifier_key`.
+        #[ink(message)]
+        pub fn verify_identity_with_signature(&mut self, payload: Vec<u8>, signature: [u8; 65]) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let attestation = <VerificationPayload as scale::Decode>::decode(&mut &payload[..])
+                .map_err(|_| "Invalid verification payload")?;
+
+            // Ensure the attestation is still valid and signed by a registered verifier's key
+            self.ensure_before_deadline(attestation.deadline)?;
+            if !self.verifiers.contains(&attestation.verifier) {
+                return Err("Only verifiers can verify identities");
+            }
+            let key = self.verifier_keys.get(&attestation.verifier).copied().ok_or("Verifier key not registered")?;
+            let digest = self.typed_digest(VERIFICATION_TYPE, &attestation);
+            if self.recover_public_key(&digest, &signature)? != key {
+                return Err("Invalid attestation signature");
+            }
+
+            self.use_nonce(attestation.verifier, attestation.nonce)?;
+
+            self.apply_verification(attestation.account, attestation.verifier, attestation.proof_hash)
+        }
+
+        /// Revoke a verified identity (only the attesting verifier or the contract owner can call this)
+        #[ink(message)]
+        pub fn revoke_identity(&mut self, account: AccountId) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            // Ensure the caller is the owner or the verifier who attested the identity
+            if caller != self.owner && identity.verifier != Some(caller) {
+                return Err("Only the attesting verifier or the owner can revoke");
+            }
+            if identity.status != Status::Verified {
+                return Err("Identity is not verified");
+            }
+
+            self.revoke(account, caller);
+            Ok(())
+        }
+
+        /// Flip lapsed verifications to `Expired`, rewarding the caller from the treasury
+        ///
+        /// Callable by anyone (keepers, or the runtime scheduler when `schedule_expiry` is enabled);
+        /// accounts that are not verified or not yet due are skipped. Fails if the treasury cannot cover the reward.
+        #[ink(message)]
+        pub fn poke_expiry(&mut self, accounts: Vec<AccountId>) -> Result<u32, &'static str> {
+            self.ensure_not_entered()?;
+            if accounts.len() > MAX_POKE_BATCH {
+                return Err("Too many accounts");
+            }
+
+            let mut expired = 0;
+            for account in accounts {
+                let due = match self.identities.get(&account) {
+                    Some(identity) => identity.status == Status::Verified && self.is_expired(identity),
+                    None => false,
+                };
+                if due {
+                    self.expire(account);
+                    expired += 1;
+                }
+            }
+
+            // Reward the keeper from the treasury
+            let reward = self
+                .config
+                .keeper_reward
+                .checked_mul(expired as Balance)
+                .ok_or("Keeper reward overflow")?;
+            if reward > 0 {
+                self.treasury_balance()
+                    .checked_sub(reward)
+                    .ok_or("Insufficient treasury balance")?;
+                let caller = self.env().caller();
+                self.treasury.total_withdrawn += reward;
+                self.env().transfer(caller, reward).map_err(|_| "Transfer failed")?;
+                self.env().emit_event(KeeperRewarded {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    keeper: caller,
+                    amount: reward,
+                });
+            }
+
+            Ok(expired)
+        }
+
+        /// Delete records whose retention window has run out (callable by anyone)
+        ///
+        /// Rejected, revoked and expired records are kept for `rejected_retention`, `revoked_retention` and
+        /// `expired_retention` after their last transition; records under legal hold, with an open dispute or renewal,
+        /// or not yet due, are skipped.
+        /// The status history, which holds no personal details, is kept.
+        #[ink(message)]
+        pub fn purge_identities(&mut self, accounts: Vec<AccountId>) -> Result<u32, &'static str> {
+            if accounts.len() > MAX_POKE_BATCH {
+                return Err("Too many accounts");
+            }
+
+            let caller = self.env().caller();
+            let mut purged = 0;
+            for account in accounts {
+                let due = match self.identities.get(&account) {
+                    Some(identity) => {
+                        self.retention_lapsed(identity)
+                            && !self.legal_holds.contains_key(&account)
+                            && !self.disputes.contains_key(&account)
+                            && !self.renewals.contains_key(&account)
+                    }
+                    None => false,
+                };
+                if due {
+                    self.purge(account, caller);
+                    purged += 1;
+                }
+            }
+            Ok(purged)
+        }
+
+        /// Register a guardian key for the caller's identity
+        ///
+        /// The guardian can only be set once, so a compromised primary key cannot replace it;
+        /// the owner can clear it to allow rotation.
+        #[ink(message)]
+        pub fn set_guardian(&mut self, key: [u8; 33]) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            if !self.identities.contains_key(&caller) {
+                return Err("Identity not found");
+            }
+            if self.guardians.contains_key(&caller) {
+                return Err("Guardian already set");
+            }
+
+            self.guardians.insert(caller, key);
+            self.env().emit_event(GuardianSet {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: caller,
+                key: Some(key),
+            });
+
+            Ok(())
+        }
+
+        /// Clear the guardian key of an identity (only contract owner can clear guardians)
+        #[ink(message)]
+        pub fn clear_guardian(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can clear guardians
+            if caller != self.owner {
+                return Err("Only the owner can clear guardians");
+            }
+
+            self.guardians.take(&account).ok_or("Guardian not set")?;
+            self.env().emit_event(GuardianSet {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                key: None,
+            });
+            Ok(())
+        }
+
+        /// Apply a revocation signed by the identity's guardian (callable by anyone)
+        ///
+        /// Freezes a pending or verified identity as `Revoked`; held fees and deposits stay
+        /// in the contract until the owner resolves the account.
+        #[ink(message)]
+        pub fn guardian_revoke(
+            &mut self,
+            account: AccountId,
+            deadline: Timestamp,
+            nonce: u64,
+            signature: [u8; 65],
+        ) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let key = self.guardians.get(&account).copied().ok_or("Guardian not set")?;
+            self.ensure_before_deadline(deadline)?;
+            let revocation = GuardianRevocation { account, deadline, nonce };
+            let digest = self.typed_digest(GUARDIAN_REVOCATION_TYPE, &revocation);
+            if self.recover_public_key(&digest, &signature)? != key {
+                return Err("Invalid guardian signature");
+            }
+            let guardian = self.account_from_public_key(&key);
+            self.use_nonce(guardian, nonce)?;
+
+            let status = self.identities.get(&account).map(|identity| identity.status).ok_or("Identity not found")?;
+            match status {
+                Status::Pending | Status::PendingReverification => self.remove_pending(account),
+                Status::Verified | Status::ChangesRequested => {}
+                Status::Approved => {
+                    self.approvals.take(&account);
+                }
+                _ => return Err("Identity is not pending or verified"),
+            }
+
+            self.revoke(account, guardian);
+            self.env().emit_event(GuardianRevoked {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                guardian,
+            });
+            Ok(())
+        }
+
+        /// Report the validity of a document, revoking the verification it backs if invalid (only oracles can call this)
+        #[ink(message)]
+        pub fn report_document_status(&mut self, document_hash: [u8; 32], status: DocumentStatus) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is a registered oracle
+            if !self.oracles.contains(&caller) {
+                return Err("Only oracles can report document status");
+            }
+
+            self.document_statuses.insert(document_hash, status);
+            self.env().emit_event(DocumentStatusReported {
+                schema_version: EVENT_SCHEMA_VERSION,
+                document_hash,
+                oracle: caller,
+                status,
+            });
+
+            // Automatically revoke a verification backed by an invalid document
+            if status == DocumentStatus::Invalid {
+                if let Some(account) = self.document_index.get(&document_hash).copied() {
+                    if self.identities.get(&account).map(|identity| identity.status) == Some(Status::Verified) {
+                        self.revoke(account, caller);
+                    }
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Add an account or document hash to the sanctions denylist (only the owner or oracles can call this)
+        ///
+        /// Denylisted accounts and documents cannot be submitted, and a verification they back is suspended.
+        #[ink(message)]
+        pub fn denylist(&mut self, entry: DenylistEntry) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is the owner or a registered oracle
+            if caller != self.owner && !self.oracles.contains(&caller) {
+                return Err("Only the owner or oracles can manage the denylist");
+            }
+            if self.denylist.contains(&entry) {
+                return Err("Already denylisted");
+            }
+
+            self.denylist.insert(entry);
+            self.env().emit_event(Denylisted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                entry,
+                by: caller,
+            });
+            self.record_admin_action(caller, AdminAction::Denylisted(entry));
+            if let Some(account) = self.account_of(entry) {
+                self.suspend(account, caller);
+            }
+            Ok(())
+        }
+
+        /// Remove an account or document hash from the sanctions denylist (only the owner or oracles can call this)
+        ///
+        /// A suspended verification is reinstated once neither its account nor its document is listed.
+        #[ink(message)]
+        pub fn delist(&mut self, entry: DenylistEntry) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is the owner or a registered oracle
+            if caller != self.owner && !self.oracles.contains(&caller) {
+                return Err("Only the owner or oracles can manage the denylist");
+            }
+            if self.denylist.take(&entry).is_none() {
+                return Err("Not denylisted");
+            }
+
+            self.env().emit_event(Delisted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                entry,
+                by: caller,
+            });
+            self.record_admin_action(caller, AdminAction::Delisted(entry));
+            if let Some(account) = self.account_of(entry) {
+                self.reinstate(account, caller);
+            }
+            Ok(())
+        }
+
+        /// Reject a pending identity (only verifiers can call this)
+        ///
+        /// Honest failures are refunded the submission fee minus the processing cut along with
+        /// the deposit; submissions rejected for `RejectReason::SuspectedFraud` forfeit both to the treasury.
+        #[ink(message)]
+        pub fn reject_identity(&mut self, account: AccountId, reason: RejectReason) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is a registered verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can reject identities");
+            }
+
+            self.apply_rejection(account, caller, reason)
+        }
+
+        /// Reject several identities in one call, returning a result per item (only verifiers can call this)
+        ///
+        /// Items rejected for `RejectReason::SuspectedFraud` forfeit their fee and deposit to the treasury; an item
+        /// whose refund the contract cannot pay out fails before any of its state changes.
+        #[ink(message)]
+        pub fn reject_identities(
+            &mut self,
+            items: Vec<(AccountId, RejectReason)>,
+        ) -> Result<Vec<Result<(), &'static str>>, &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is a registered verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can reject identities");
+            }
+            if items.len() > MAX_REVIEW_BATCH {
+                return Err("Too many identities");
+            }
+
+            Ok(items
+                .into_iter()
+                .map(|(account, reason)| self.apply_rejection(account, caller, reason))
+                .collect())
+        }
+
+        /// Attest the next KYC tier of a verified identity (only verifiers permitted for that tier can call this)
+        #[ink(message)]
+        pub fn attest_kyc_level(&mut self, account: AccountId, level: KycLevel) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure the caller is a registered verifier permitted to attest this tier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can attest KYC levels");
+            }
+            if self.verifier_tiers.get(&caller).copied().unwrap_or(KycLevel::Basic) < level {
+                return Err("Verifier not permitted for this KYC level");
+            }
+            self.ensure_not_paused()?;
+
+            // Ensure the identity is verified and the tiers below have been attested
+            if !self.is_verified(account) {
+                return Err("Identity is not verified");
+            }
+            let required = match level {
+                KycLevel::Basic => return Err("Basic level is granted by verification"),
+                KycLevel::Document => KycLevel::Basic,
+                KycLevel::Biometric => KycLevel::Document,
+            };
+            if level == KycLevel::Biometric && !self.has_liveness(account) {
+                return Err("Liveness check not attested");
+            }
+            match self.kyc_levels.get(&account).copied() {
+                Some(current) if current >= level => return Err("KYC level already attested"),
+                Some(current) if current >= required => {}
+                _ => return Err("Lower KYC level not attested"),
+            }
+
+            self.kyc_levels.insert(account, level);
+            self.kyc_attestations.insert((account, level), caller);
+            self.env().emit_event(KycLevelAttested {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier: caller,
+                level,
+            });
+            Ok(())
+        }
+
+        /// Return a pending identity to the holder for corrections (only verifiers can call this)
+        ///
+        /// The held fee and deposit stay with the identity, and the holder resubmits the
+        /// corrected details with `update_identity`. Counts as a failed attempt.
+        #[ink(message)]
+        pub fn request_changes(&mut self, account: AccountId, reason: RejectReason) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure the caller is a registered verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can request changes");
+            }
+            self.ensure_enabled(MESSAGE_REJECT)?;
+            self.ensure_routed_to(account, caller)?;
+
+            // Ensure the identity exists and is awaiting verification
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            if !identity.status.is_pending() {
+                return Err("Identity is not pending verification");
+            }
+            identity.status = Status::ChangesRequested;
+            identity.verifier = Some(caller);
+            identity.reject_reason = Some(reason);
+            self.remove_pending(account);
+            self.clear_sign_offs(account);
+            self.record_history(account, Status::ChangesRequested, caller, "Changes requested");
+
+            self.env().emit_event(ChangesRequested {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier: caller,
+                reason,
+            });
+            self.record_failed_attempt(account);
+            Ok(())
+        }
+
+        /// Route a pending identity to a specific verifier, or clear the routing with `None`
+        ///
+        /// Callable by the holder, an operator with `PERMISSION_UPDATE`, or the owner.
+        #[ink(message)]
+        pub fn assign_verifier(&mut self, account: AccountId, verifier: Option<AccountId>) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                self.ensure_holder_or_operator(account, caller, PERMISSION_UPDATE)?;
+            }
+            if let Some(verifier) = verifier {
+                if !self.verifiers.contains(&verifier) {
+                    return Err("Preferred verifier is not registered");
+                }
+            }
+
+            // Ensure the identity exists and is still awaiting a decision
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+            if !identity.status.is_pending() && identity.status != Status::ChangesRequested {
+                return Err("Identity is not pending verification");
+            }
+            if let Some(verifier) = verifier {
+                if !self.accreditations.contains(&(verifier, identity.jurisdiction)) {
+                    return Err("Preferred verifier is not accredited for the jurisdiction");
+                }
+                if !self.is_specialized(verifier, identity.document_kind) {
+                    return Err("Preferred verifier lacks the specialization for the document kind");
+                }
+            }
+
+            self.set_assigned_verifier(account, caller, verifier);
+            Ok(())
+        }
+
+        /// Move a pending submission into the priority lane by paying at least the expedite fee (callable by anyone)
+        ///
+        /// The priority fee goes in full to the verifier that decides on the submission.
+        #[ink(message, payable)]
+        pub fn expedite_identity(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let amount = self.env().transferred_value();
+            let expedite_fee = self.fees.fee(FeeKind::Expedite);
+            if expedite_fee == 0 {
+                return Err("Priority lane is disabled");
+            }
+            if amount < expedite_fee {
+                return Err("Insufficient priority fee");
+            }
+            if self.priority_positions.get(&account).is_some() {
+                return Err("Identity already expedited");
+            }
+
+            // Ensure the identity exists and is still awaiting verification
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            if !identity.status.is_pending() {
+                return Err("Identity is not pending verification");
+            }
+            identity.priority_fee = identity.priority_fee.saturating_add(amount);
+
+            self.enqueue_pending(account, true);
+            self.env().emit_event(IdentityExpedited {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                paid_by: caller,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Cancel a targeted request whose deadline has passed, refunding any escrowed bounty to the holder (callable by anyone)
+        ///
+        /// The lapse counts as a timeout and SLA breach against the verifier. The holder can then re-route the request
+        /// with `assign_verifier`; until then it is back in the open pool and any verifier may act on it.
+        #[ink(message)]
+        pub fn cancel_assignment(&mut self, account: AccountId) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+            if !identity.status.is_pending() && identity.status != Status::ChangesRequested {
+                return Err("Identity is not pending verification");
+            }
+            let verifier = identity.assigned_verifier.ok_or("Identity is not assigned")?;
+            let jurisdiction = identity.jurisdiction;
+            let document_kind = identity.document_kind;
+            let deadline = identity.assignment_deadline.unwrap_or_default();
+            if !self.assignment_lapsed(identity) {
+                return Err("Assignment deadline has not passed");
+            }
+
+            if let Some(identity) = self.identities.get_mut(&account) {
+                identity.assigned_verifier = None;
+                identity.assignment_deadline = None;
+                identity.assigned_at = None;
+            }
+            self.adjust_workload(verifier, false);
+
+            // Count the missed deadline against the verifier's SLA record
+            let mut stats = self.verifier_stats.get(&verifier).copied().unwrap_or_default();
+            stats.sla_breaches += 1;
+            self.verifier_stats.insert(verifier, stats);
+            let timeouts = self.verifier_timeouts.get(&verifier).copied().unwrap_or(0);
+            self.verifier_timeouts.insert(verifier, timeouts + 1);
+            let reputation = self.verifier_reputation.get(&verifier).copied().unwrap_or(0);
+            self.verifier_reputation.insert(verifier, reputation.saturating_sub(1));
+            self.env().emit_event(VerificationTimedOut {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier,
+                deadline,
+            });
+            if let Some(position) = self.pending_positions.get(&account).copied() {
+                self.env().emit_event(WorkExpired {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    verifier,
+                    deadline,
+                    position,
+                });
+            }
+
+            let bounty_refunded = self.bounties.take(&account).map_or(0, |bounty| bounty.amount);
+            if bounty_refunded > 0 {
+                self.env().transfer(account, bounty_refunded).map_err(|_| "Transfer failed")?;
+            }
+            self.env().emit_event(AssignmentCancelled {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier,
+                bounty_refunded,
+            });
+
+            // Re-route to the least loaded of the other verifiers
+            if self.config.auto_assign {
+                if let Some(next) = self.least_loaded_verifier(jurisdiction, document_kind, Some(verifier)) {
+                    let contract = self.env().account_id();
+                    self.set_assigned_verifier(account, contract, Some(next));
+                }
+            }
+            Ok(())
+        }
+
+        /// Escrow a bounty for whichever verifier verifies the identity (holder or an operator with `PERMISSION_BOUNTY`)
+        #[ink(message, payable)]
+        pub fn post_bounty(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            self.ensure_holder_or_operator(account, caller, PERMISSION_BOUNTY)?;
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err("Bounty must be non-zero");
+            }
+
+            // Ensure the identity exists and is still awaiting verification
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+            if !identity.status.is_pending() {
+                return Err("Identity is not pending verification");
+            }
+
+            // Add to any existing bounty and restart its timeout
+            let total = self.bounties.get(&account).map_or(0, |bounty| bounty.amount) + amount;
+            self.bounties.insert(account, Bounty {
+                amount: total,
+                posted_at: self.env().block_timestamp(),
+            });
+
+            self.env().emit_event(BountyPosted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                amount,
+                total,
+            });
+
+            Ok(())
+        }
+
+        /// Return an escrowed bounty that no verifier picked up before the timeout to the holder
+        /// (holder or an operator with `PERMISSION_BOUNTY`)
+        #[ink(message)]
+        pub fn reclaim_bounty(&mut self, account: AccountId) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            self.ensure_holder_or_operator(account, caller, PERMISSION_BOUNTY)?;
+            let bounty = self.bounties.get(&account).ok_or("No bounty posted")?;
+
+            // Ensure the bounty timeout has elapsed
+            if self.env().block_timestamp() < bounty.posted_at + self.config.bounty_timeout {
+                return Err("Bounty timeout has not elapsed");
+            }
+
+            let amount = bounty.amount;
+            self.bounties.take(&account);
+            self.env().transfer(account, amount).map_err(|_| "Transfer failed")?;
+
+            self.env().emit_event(BountyReclaimed {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Accept the current terms of service for the caller's identity
+        #[ink(message)]
+        pub fn accept_terms(&mut self, terms_hash: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            let version = self.ensure_current_terms(terms_hash)?;
+            let now = self.env().block_timestamp();
+            let identity = self.identities.get_mut(&caller).ok_or("Identity not found")?;
+            identity.terms_version = version;
+            identity.terms_accepted_at = now;
+            self.env().emit_event(TermsAccepted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: caller,
+                version,
+            });
+            self.emit_consent_receipt(caller, None, CONSENT_PURPOSE_VERIFICATION, DATA_CATEGORY_ALL);
+            Ok(())
+        }
+
+        /// Ask for the caller's personal details to be erased within `erasure_deadline`
+        #[ink(message)]
+        pub fn request_erasure(&mut self) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            let identity = self.identities.get(&caller).ok_or("Identity not found")?;
+            if identity.erased_at.is_some() {
+                return Err("Identity already erased");
+            }
+            match identity.status {
+                Status::Pending | Status::PendingReverification | Status::ChangesRequested | Status::Approved => {
+                    return Err("Identity is awaiting a decision");
+                }
+                _ => {}
+            }
+            if self.erasure_requests.contains_key(&caller) {
+                return Err("Erasure already requested");
+            }
+
+            let deadline = self.env().block_timestamp() + self.config.erasure_deadline;
+            self.erasure_requests.insert(caller, deadline);
+            self.env().emit_event(ErasureRequested {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: caller,
+                deadline,
+            });
+            Ok(())
+        }
+
+        /// Replace a holder's personal details with a tombstone (the owner, or the holder once the deadline has passed)
+        ///
+        /// The status, verifier, document hash and proof hash are kept so past decisions stay defensible.
+        #[ink(message)]
+        pub fn erase_identity(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let deadline = self.erasure_requests.get(&account).copied().ok_or("No erasure requested")?;
+            let now = self.env().block_timestamp();
+            // Ensure the caller is the owner, or the holder after the owner missed the deadline
+            if caller != self.owner && !(self.holder_of(caller) == Ok(account) && now >= deadline) {
+                return Err("Only the owner can erase before the deadline");
+            }
+            if self.legal_holds.contains_key(&account) {
+                return Err("Identity is under legal hold");
+            }
+
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            identity.name = [0; MAX_NAME_LENGTH];
+            identity.age = 0;
+            identity.document_id = [0; MAX_DOCUMENT_ID_LENGTH];
+            identity.notes_hash = None;
+            identity.erased_at = Some(now);
+            let status = identity.status;
+            self.erasure_requests.take(&account);
+            self.clear_sensitive_data(account);
+            self.record_history_note(account, status, caller, "Erased");
+            if caller == self.owner {
+                self.record_admin_action(caller, AdminAction::IdentityErased(account));
+            }
+
+            self.env().emit_event(Erased {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                erased_by: caller,
+                deadline,
+            });
+            Ok(())
+        }
+
+        /// Check whether an account or document hash is on the sanctions denylist
+        #[ink(message)]
+        pub fn is_denylisted(&self, entry: DenylistEntry) -> bool {
+            self.denylist.contains(&entry)
+        }
+
+        /// Get the age of majority applied to a jurisdiction
+        #[ink(message)]
+        pub fn get_age_of_majority(&self, jurisdiction: [u8; 2]) -> u32 {
+            self.age_of_majority(jurisdiction)
+        }
+
+        /// Get the rules applied to a document kind
+        #[ink(message)]
+        pub fn get_document_rules(&self, document_kind: DocumentKind) -> DocumentRules {
+            self.document_rules_of(document_kind)
+        }
+
+        /// Get the `SPECIALIZATION_*` bits held by a verifier
+        #[ink(message)]
+        pub fn get_verifier_specializations(&self, verifier: AccountId) -> u32 {
+            self.verifier_specializations.get(&verifier).copied().unwrap_or(0)
+        }
+
+        /// Check whether a verified holder has reached the age of majority of their jurisdiction
+        #[ink(message)]
+        pub fn is_adult(&self, account: AccountId) -> bool {
+            if !self.is_verified(account) {
+                return false;
+            }
+            self.identities
+                .get(&account)
+                .map_or(false, |identity| identity.age >= self.age_of_majority(identity.jurisdiction))
+        }
+
+        /// Check whether a verifier is accredited for a jurisdiction
+        #[ink(message)]
+        pub fn is_accredited(&self, verifier: AccountId, jurisdiction: [u8; 2]) -> bool {
+            self.accreditations.contains(&(verifier, jurisdiction))
+        }
+
+        /// Get the current terms-of-service version (0 until terms are published)
+        #[ink(message)]
+        pub fn get_terms_version(&self) -> u32 {
+            self.terms_version
+        }
+
+        /// Get the hash of a published terms-of-service version
+        #[ink(message)]
+        pub fn get_terms(&self, version: u32) -> Option<[u8; 32]> {
+            self.terms.get(&version).copied()
+        }
+
+        /// Check whether a holder must accept the current terms, having accepted only a version that is no longer sufficient
+        #[ink(message)]
+        pub fn needs_terms_acceptance(&self, account: AccountId) -> bool {
+            self.identities
+                .get(&account)
+                .map_or(false, |identity| identity.terms_version < self.min_terms_version)
+        }
+
+        /// Get the processing deadline of an open erasure request, if any
+        #[ink(message)]
+        pub fn get_erasure_request(&self, account: AccountId) -> Option<Timestamp> {
+            self.erasure_requests.get(&account).copied()
+        }
+
+        /// Start renewing the caller's verification within `renewal_window` of its expiry
+        /// (must be accompanied by the current submission fee and deposit)
+        #[ink(message, payable)]
+        pub fn start_renewal(&mut self, proof_hash: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            self.ensure_enabled(MESSAGE_RENEWAL)?;
+            if self.config.renewal_window == 0 {
+                return Err("Renewals are disabled");
+            }
+            if !self.is_verified(caller) {
+                return Err("Identity is not verified");
+            }
+            if self.renewals.get(&caller).is_some() {
+                return Err("Renewal already requested");
+            }
+            if self.needs_terms_acceptance(caller) {
+                return Err("Current terms must be accepted");
+            }
+
+            // Ensure the verification expires within the renewal window
+            let now = self.env().block_timestamp();
+            let expires_at = self.identities.get(&caller).and_then(|identity| identity.expires_at);
+            match expires_at {
+                Some(expires_at) if now + self.config.renewal_window >= expires_at => {}
+                Some(_) => return Err("Renewal window has not opened"),
+                None => return Err("Verification does not expire"),
+            }
+
+            let (fee_paid, deposit) = self.collect_payment(self.quote_fee(FeeKind::Renewal, caller))?;
+            self.renewals.insert(caller, Renewal {
+                proof_hash,
+                fee_paid,
+                deposit,
+                requested_at: now,
+            });
+            if self.notifies(caller, NOTIFY_RENEWAL) {
+                self.env().emit_event(RenewalRequested {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account: caller,
+                    proof_hash,
+                });
+            }
+            Ok(())
+        }
+
+        /// Approve a requested renewal (only verifiers can call this)
+        ///
+        /// The renewed verification runs from the old expiry, so the holder sees no gap in status.
+        #[ink(message)]
+        pub fn verify_renewal(&mut self, account: AccountId, proof_hash: [u8; 32]) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is a registered verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can verify renewals");
+            }
+            self.ensure_enabled(MESSAGE_RENEWAL)?;
+            let renewal = self.renewals.get(&account).cloned().ok_or("No renewal requested")?;
+            if renewal.proof_hash != proof_hash {
+                return Err("Proof hash does not match");
+            }
+            if !self.is_verified(account) {
+                return Err("Identity is not verified");
+            }
+            let (jurisdiction, credential_type, document_kind) = self
+                .identities
+                .get(&account)
+                .map(|identity| (identity.jurisdiction, identity.credential_type, identity.document_kind))
+                .ok_or("Identity not found")?;
+            if !self.accreditations.contains(&(caller, jurisdiction)) {
+                return Err("Verifier not accredited for the jurisdiction");
+            }
+            if !self.is_specialized(caller, document_kind) {
+                return Err("Verifier lacks the specialization for the document kind");
+            }
+
+            // Extend the verification from its current expiry
+            let now = self.env().block_timestamp();
+            let validity = self.verification_validity(credential_type, document_kind);
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            identity.set_proof_hash(proof_hash, now);
+            if let Some(entry) = identity.proofs.first_mut() {
+                entry.attested_by = Some(caller);
+            }
+            identity.verifier = Some(caller);
+            identity.verified_at = now;
+            identity.expires_at = match (validity, identity.expires_at) {
+                (0, _) => None,
+                (validity, Some(expires_at)) => Some(expires_at + validity),
+                (validity, None) => Some(now + validity),
+            };
+            let expires_at = identity.expires_at;
+            let lapses_at = identity.lapses_at();
+            self.renewals.take(&account);
+            let renewal_count = self.renewal_counts.get(&account).copied().unwrap_or(0);
+            self.renewal_counts.insert(account, renewal_count + 1);
+            self.record_history(account, Status::Verified, caller, "Renewed");
+
+            if self.notifies(account, NOTIFY_RENEWAL) {
+                self.env().emit_event(IdentityRenewed {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    verifier: caller,
+                    expires_at,
+                });
+            }
+            self.issue_certificate(account, caller);
+            if let Some(lapses_at) = lapses_at {
+                self.schedule_expiry(account, lapses_at);
+            }
+
+            self.split_fee(account, caller, renewal.fee_paid);
+            if renewal.deposit > 0 {
+                self.env().transfer(account, renewal.deposit).map_err(|_| "Transfer failed")?;
+                self.env().emit_event(DepositReturned {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    amount: renewal.deposit,
+                });
+            }
+            Ok(())
+        }
+
+        /// Attach the hash of review notes / an evidence bundle to a decision (only the deciding verifier)
+        #[ink(message)]
+        pub fn attach_attestation_notes(&mut self, account: AccountId, notes_hash: [u8; 32]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            // Ensure the caller is the verifier that decided on the identity
+            if identity.verifier != Some(caller) {
+                return Err("Only the deciding verifier can attach notes");
+            }
+
+            identity.notes_hash = Some(notes_hash);
+            self.env().emit_event(AttestationNotesAttached {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier: caller,
+                notes_hash,
+            });
+            Ok(())
+        }
+
+        /// Appeal the rejection of the caller's identity by posting at least `dispute_bond`
+        #[ink(message, payable)]
+        pub fn open_appeal(&mut self) -> Result<(), &'static str> {
+            let caller = self.holder_of(self.env().caller())?;
+            let identity = self.identities.get(&caller).ok_or("Identity not found")?;
+            if identity.status != Status::Rejected {
+                return Err("Identity is not rejected");
+            }
+            let verifier = identity.verifier;
+            self.open_dispute(caller, caller, DisputeKind::Appeal, verifier)
+        }
+
+        /// Challenge the verification of an identity by posting at least `dispute_bond`
+        #[ink(message, payable)]
+        pub fn challenge_verification(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            if !self.is_verified(account) {
+                return Err("Identity is not verified");
+            }
+            let verifier = self.identities.get(&account).and_then(|identity| identity.verifier);
+            self.open_dispute(account, caller, DisputeKind::Challenge, verifier)
+        }
+
+        /// Adjudicate an open dispute (only the owner or a verifier other than the disputed one)
+        ///
+        /// An upheld appeal returns the identity to the verification queue and an upheld challenge
+        /// revokes it; the disputant's bond is refunded. A dismissed dispute forfeits the bond to
+        /// the treasury. The disputed verifier's reputation moves with the outcome.
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, account: AccountId, upheld: bool) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            let dispute = self.disputes.get(&account).cloned().ok_or("No open dispute")?;
+            // Ensure the caller is the owner or an independent verifier
+            if caller != self.owner && (!self.verifiers.contains(&caller) || dispute.verifier == Some(caller)) {
+                return Err("Only the owner or another verifier can resolve disputes");
+            }
+            self.disputes.take(&account);
+
+            if upheld {
+                match dispute.kind {
+                    DisputeKind::Appeal => {
+                        if let Some(identity) = self.identities.get_mut(&account) {
+                            identity.status = Status::Pending;
+                            identity.reject_reason = None;
+                            identity.assigned_verifier = None;
+                            identity.assignment_deadline = None;
+                            identity.assigned_at = None;
+                            identity.failed_attempts = identity.failed_attempts.saturating_sub(1);
+                        }
+                        self.enqueue_pending(account, false);
+                        self.record_history(account, Status::Pending, caller, "Appeal upheld");
+                    }
+                    // The identity may have been revoked or expired while the challenge was open
+                    DisputeKind::Challenge => {
+                        let disputes = self.dispute_counts.get(&account).copied().unwrap_or(0);
+                        self.dispute_counts.insert(account, disputes + 1);
+                        if self.identities.get(&account).map(|identity| identity.status) == Some(Status::Verified) {
+                            self.revoke(account, caller);
+                        }
+                    }
+                }
+                self.env().transfer(dispute.disputant, dispute.bond).map_err(|_| "Transfer failed")?;
+            } else {
+                self.treasury.total_collected += dispute.bond;
+            }
+
+            // Credit or debit the verifier whose decision was disputed
+            if let Some(verifier) = dispute.verifier {
+                let reputation = self.verifier_reputation.get(&verifier).copied().unwrap_or(0);
+                let change = if upheld { -1 } else { 1 };
+                self.verifier_reputation.insert(verifier, reputation.saturating_add(change));
+            }
+
+            self.env().emit_event(DisputeResolved {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                adjudicator: caller,
+                kind: dispute.kind,
+                upheld,
+            });
+            Ok(())
+        }
+
+        /// Prepay for one or more subscription periods of privileged queries
+        #[ink(message, payable)]
+        pub fn subscribe(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            let amount = self.env().transferred_value();
+            // Ensure subscriptions are offered and at least one period is paid for
+            if self.config.subscription_price == 0 || self.config.subscription_period == 0 {
+                return Err("Subscriptions are not enabled");
+            }
+            let periods = amount / self.config.subscription_price;
+            if periods == 0 {
+                return Err("Insufficient subscription payment");
+            }
+            if amount % self.config.subscription_price != 0 {
+                return Err("Payment must cover whole subscription periods");
+            }
+            self.treasury.total_collected += amount;
+
+            // Extend from the current expiry if the subscription is still active
+            let now = self.env().block_timestamp();
+            let start = self.subscriptions.get(&caller).copied().filter(|expires_at| *expires_at > now).unwrap_or(now);
+            let expires_at = start + periods as Timestamp * self.config.subscription_period;
+            self.subscriptions.insert(caller, expires_at);
+
+            self.env().emit_event(Subscribed {
+                schema_version: EVENT_SCHEMA_VERSION,
+                subscriber: caller,
+                amount,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Claim the caller's accrued verifier earnings
+        #[ink(message)]
+        pub fn claim_earnings(&mut self) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            let amount = self.earnings.take(&caller).unwrap_or(0);
+            if amount == 0 {
+                return Err("No earnings to claim");
+            }
+
+            self.env().transfer(caller, amount).map_err(|_| "Transfer failed")?;
+            self.env().emit_event(EarningsClaimed {
+                schema_version: EVENT_SCHEMA_VERSION,
+                verifier: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Fund the pool reimbursing relayers for sponsored submissions (only contract owner can fund it)
+        #[ink(message, payable)]
+        pub fn fund_sponsorship_pool(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can fund the sponsorship pool
+            if caller != self.owner {
+                return Err("Only the owner can fund the sponsorship pool");
+            }
+
+            let amount = self.env().transferred_value();
+            self.sponsorship_pool += amount;
+            self.env().emit_event(SponsorshipPoolFunded {
+                schema_version: EVENT_SCHEMA_VERSION,
+                amount,
+                total: self.sponsorship_pool,
+            });
+
+            Ok(())
+        }
+
+        /// Add a new relayer (only contract owner can add relayers)
+        #[ink(message)]
+        pub fn add_relayer(&mut self, relayer: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can add relayers
+            if caller != self.owner {
+                return Err("Only the owner can add relayers");
+            }
+
+            self.relayers.insert(relayer);
+            self.record_admin_action(caller, AdminAction::RelayerAdded(relayer));
+            Ok(())
+        }
+
+        /// Remove a relayer (only contract owner can remove relayers)
+        #[ink(message)]
+        pub fn remove_relayer(&mut self, relayer: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can remove relayers
+            if caller != self.owner {
+                return Err("Only the owner can remove relayers");
+            }
+
+            self.relayers.take(&relayer);
+            self.record_admin_action(caller, AdminAction::RelayerRemoved(relayer));
+            Ok(())
+        }
+
+        /// Register a contract to be notified of verification status changes (only contract owner can add hooks)
+        #[ink(message)]
+        pub fn add_hook(&mut self, hook: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can add hooks
+            if caller != self.owner {
+                return Err("Only the owner can add hooks");
+            }
+            if self.hooks.iter().any(|registered| *registered == hook) {
+                return Err("Hook already registered");
+            }
+            if self.hooks.len() >= MAX_HOOKS {
+                return Err("Too many hooks");
+            }
+
+            self.hooks.push(hook);
+            self.record_admin_action(caller, AdminAction::HookAdded(hook));
+            Ok(())
+        }
+
+        /// Unregister a verification hook (only contract owner can remove hooks)
+        #[ink(message)]
+        pub fn remove_hook(&mut self, hook: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can remove hooks
+            if caller != self.owner {
+                return Err("Only the owner can remove hooks");
+            }
+
+            let index = self.hooks.iter().position(|registered| *registered == hook).ok_or("Hook not found")?;
+            let last = self.hooks.len() - 1;
+            self.hooks.swap(index as u32, last);
+            self.hooks.pop();
+            self.record_admin_action(caller, AdminAction::HookRemoved(hook));
+            Ok(())
+        }
+
+        /// Allow a relying-party contract to call detail-level getters (only contract owner can add relying parties)
+        #[ink(message)]
+        pub fn add_relying_party(&mut self, relying_party: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can add relying parties
+            if caller != self.owner {
+                return Err("Only the owner can add relying parties");
+            }
+
+            self.relying_parties.insert(relying_party);
+            self.record_admin_action(caller, AdminAction::RelyingPartyAdded(relying_party));
+            Ok(())
+        }
+
+        /// Remove a relying-party contract from the allowlist (only contract owner can remove relying parties)
+        #[ink(message)]
+        pub fn remove_relying_party(&mut self, relying_party: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can remove relying parties
+            if caller != self.owner {
+                return Err("Only the owner can remove relying parties");
+            }
+
+            self.relying_parties.take(&relying_party);
+            self.record_admin_action(caller, AdminAction::RelyingPartyRemoved(relying_party));
+            Ok(())
+        }
+
+        /// Register a sibling-parachain contract to receive attestations (only contract owner can add targets)
+        #[ink(message)]
+        pub fn add_xcm_target(&mut self, para_id: u32, contract: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can add XCM targets
+            if caller != self.owner {
+                return Err("Only the owner can add XCM targets");
+            }
+            let target = XcmTarget { para_id, contract };
+            if self.xcm_targets.iter().any(|registered| *registered == target) {
+                return Err("XCM target already registered");
+            }
+            if self.xcm_targets.len() >= MAX_XCM_TARGETS {
+                return Err("Too many XCM targets");
+            }
+
+            self.xcm_targets.push(target);
+            self.record_admin_action(caller, AdminAction::XcmTargetAdded(para_id, contract));
+            Ok(())
+        }
+
+        /// Unregister a sibling-parachain attestation target (only contract owner can remove targets)
+        #[ink(message)]
+        pub fn remove_xcm_target(&mut self, para_id: u32, contract: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can remove XCM targets
+            if caller != self.owner {
+                return Err("Only the owner can remove XCM targets");
+            }
+
+            let target = XcmTarget { para_id, contract };
+            let index = self
+                .xcm_targets
+                .iter()
+                .position(|registered| *registered == target)
+                .ok_or("XCM target not found")?;
+            let last = self.xcm_targets.len() - 1;
+            self.xcm_targets.swap(index as u32, last);
+            self.xcm_targets.pop();
+            self.record_admin_action(caller, AdminAction::XcmTargetRemoved(para_id, contract));
+            Ok(())
+        }
+
+        /// Send the current verification status of an account to every registered sibling-parachain target
+        #[ink(message)]
+        pub fn broadcast_attestation(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+            let payload = scale::Encode::encode(&XcmAttestation {
+                account,
+                status: self.effective_status(identity),
+                verifier: identity.verifier,
+                block_number: self.env().block_number(),
+            });
+
+            for target in self.xcm_targets.iter() {
+                self.env()
+                    .extension()
+                    .send_xcm(target.para_id, target.contract, payload.clone())
+                    .map_err(|_| "XCM send failed")?;
+                self.env().emit_event(AttestationBroadcast {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    para_id: target.para_id,
+                    contract: target.contract,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Register the caller's compressed ECDSA public key (only verifiers can call this)
+        #[ink(message)]
+        pub fn set_verifier_key(&mut self, key: [u8; 33]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure the caller is a registered verifier
+            if !self.verifiers.contains(&caller) {
+                return Err("Only verifiers can register keys");
+            }
+            // Ensure the key is a valid compressed public key
+            self.env().ecdsa_to_eth_address(&key).map_err(|_| "Invalid ECDSA key")?;
+
+            self.verifier_keys.insert(caller, key);
+            self.env().emit_event(VerifierKeySet {
+                schema_version: EVENT_SCHEMA_VERSION,
+                verifier: caller,
+                key,
+            });
+
+            Ok(())
+        }
+
+        /// Add a new oracle (only contract owner can add oracles)
+        #[ink(message)]
+        pub fn add_oracle(&mut self, oracle: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can add oracles
+            if caller != self.owner {
+                return Err("Only the owner can add oracles");
+            }
+
+            self.oracles.insert(oracle);
+            self.record_admin_action(caller, AdminAction::OracleAdded(oracle));
+            Ok(())
+        }
+
+        /// Remove an oracle (only contract owner can remove oracles)
+        #[ink(message)]
+        pub fn remove_oracle(&mut self, oracle: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can remove oracles
+            if caller != self.owner {
+                return Err("Only the owner can remove oracles");
+            }
+
+            self.oracles.take(&oracle);
+            self.record_admin_action(caller, AdminAction::OracleRemoved(oracle));
+            Ok(())
+        }
+
+        /// Set the key signing exportable attestations (only contract owner can set it)
+        #[ink(message)]
+        pub fn set_attestation_key(&mut self, key: [u8; 33]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can set the attestation key
+            if caller != self.owner {
+                return Err("Only the owner can set the attestation key");
+            }
+
+            self.attestation_key = Some(key);
+            self.env().emit_event(AttestationKeySet {
+                schema_version: EVENT_SCHEMA_VERSION,
+                key,
+            });
+            self.record_admin_action(caller, AdminAction::AttestationKeySet);
+            Ok(())
+        }
+
+        /// Record a signed, expiring statement of an account's current verification status
+        ///
+        /// The statement is built on-chain from the current status; `signature` must be made by the
+        /// attestation key over the Blake2x256 hash of the SCALE-encoded statement. The caller pays exactly the
+        /// presentation fee, which goes to the treasury.
+        #[ink(message, payable)]
+        pub fn sign_attestation(
+            &mut self,
+            account: AccountId,
+            issued_at: Timestamp,
+            signature: [u8; 65],
+        ) -> Result<(), &'static str> {
+            let key = self.attestation_key.ok_or("Attestation key not set")?;
+            let transferred = self.env().transferred_value();
+            // Require the exact fee so an overpayment is never kept
+            if transferred != self.fees.fee(FeeKind::Presentation) {
+                return Err("Payment must equal the presentation fee");
+            }
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+
+            // Ensure the statement is currently valid
+            let now = self.env().block_timestamp();
+            let expires_at = issued_at + self.config.attestation_validity;
+            if issued_at > now || expires_at <= now {
+                return Err("Attestation not currently valid");
+            }
+
+            let statement = AttestationStatement {
+                contract: self.env().account_id(),
+                account,
+                status: self.effective_status(identity),
+                issued_at,
+                expires_at,
+            };
+            let digest = self.env().hash_encoded::<ink_env::hash::Blake2x256, _>(&statement);
+            if self.recover_public_key(&digest, &signature)? != key {
+                return Err("Invalid attestation signature");
+            }
+
+            self.env().emit_event(AttestationSigned {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                status: statement.status,
+                expires_at,
+            });
+            self.signed_attestations.insert(account, SignedAttestation { statement, signature });
+            self.treasury.total_collected += transferred;
+
+            Ok(())
+        }
+
+        /// Add a new verifier (only contract owner can add verifiers)
+        #[ink(message)]
+        pub fn add_verifier(&mut self, verifier: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can add verifiers
+            if caller != self.owner {
+                return Err("Only the owner can add verifiers");
+            }
+
+            // Add the verifier to the set of verifiers
+            self.verifiers.insert(verifier);
+            self.record_admin_action(caller, AdminAction::VerifierAdded(verifier));
+            Ok(())
+        }
+
+        /// Remove a verifier (only contract owner can remove verifiers)
+        #[ink(message)]
+        pub fn remove_verifier(&mut self, verifier: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can remove verifiers
+            if caller != self.owner {
+                return Err("Only the owner can remove verifiers");
+            }
+
+            // Remove the verifier from the set of verifiers
+            self.verifiers.take(&verifier);
+            self.record_admin_action(caller, AdminAction::VerifierRemoved(verifier));
+            Ok(())
+        }
+
+        /// Set the highest KYC tier a verifier may attest (only contract owner can set it)
+        #[ink(message)]
+        pub fn set_verifier_tier(&mut self, verifier: AccountId, level: KycLevel) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can set verifier tiers
+            if caller != self.owner {
+                return Err("Only the owner can set verifier tiers");
+            }
+
+            self.verifier_tiers.insert(verifier, level);
+            self.record_admin_action(caller, AdminAction::VerifierTierSet(verifier, level));
+            Ok(())
+        }
+
+        /// Accredit a verifier to attest identities from a jurisdiction (only contract owner can accredit verifiers)
+        #[ink(message)]
+        pub fn accredit_verifier(&mut self, verifier: AccountId, jurisdiction: [u8; 2]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can accredit verifiers
+            if caller != self.owner {
+                return Err("Only the owner can accredit verifiers");
+            }
+
+            self.accreditations.insert((verifier, jurisdiction));
+            self.record_admin_action(caller, AdminAction::VerifierAccredited(verifier, jurisdiction));
+            Ok(())
+        }
+
+        /// Withdraw a verifier's accreditation for a jurisdiction (only contract owner can revoke accreditations)
+        #[ink(message)]
+        pub fn revoke_accreditation(&mut self, verifier: AccountId, jurisdiction: [u8; 2]) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can revoke accreditations
+            if caller != self.owner {
+                return Err("Only the owner can revoke accreditations");
+            }
+
+            self.accreditations.take(&(verifier, jurisdiction));
+            self.record_admin_action(caller, AdminAction::AccreditationRevoked(verifier, jurisdiction));
+            Ok(())
+        }
+
+        /// Set the age of majority for a jurisdiction, or fall back to `minor_age_threshold` with `None`
+        /// (only contract owner can set it)
+        #[ink(message)]
+        pub fn set_age_of_majority(&mut self, jurisdiction: [u8; 2], age: Option<u32>) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can set ages of majority
+            if caller != self.owner {
+                return Err("Only the owner can set ages of majority");
+            }
+
+            match age {
+                Some(age) => {
+                    self.ages_of_majority.insert(jurisdiction, age);
+                }
+                None => {
+                    self.ages_of_majority.take(&jurisdiction);
+                }
+            }
+            self.record_admin_action(caller, AdminAction::AgeOfMajoritySet(jurisdiction, age));
+            Ok(())
+        }
+
+        /// Set the rules applied to submissions backed by `document_kind` (only contract owner can set them)
+        #[ink(message)]
+        pub fn set_document_rules(&mut self, document_kind: DocumentKind, rules: DocumentRules) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can set document rules
+            if caller != self.owner {
+                return Err("Only the owner can set document rules");
+            }
+
+            self.document_rules.insert(document_kind, rules);
+            self.record_admin_action(caller, AdminAction::DocumentRulesSet(document_kind));
+            Ok(())
+        }
+
+        /// Set the `SPECIALIZATION_*` bits held by a verifier (only contract owner can set them)
+        #[ink(message)]
+        pub fn set_verifier_specializations(&mut self, verifier: AccountId, specializations: u32) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can set verifier specializations
+            if caller != self.owner {
+                return Err("Only the owner can set verifier specializations");
+            }
+            if !self.verifiers.contains(&verifier) {
+                return Err("Verifier not found");
+            }
+
+            self.verifier_specializations.insert(verifier, specializations);
+            self.record_admin_action(caller, AdminAction::VerifierSpecializationsSet(verifier, specializations));
+            Ok(())
+        }
+
+        /// Update the contract configuration (only contract owner can update it)
+        #[ink(message)]
+        pub fn set_config(&mut self, config: Config) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can update the configuration
+            if caller != self.owner {
+                return Err("Only the owner can update the configuration");
+            }
+
+            self.config = config;
+            self.record_admin_action(caller, AdminAction::ConfigChanged);
+            Ok(())
+        }
+
+        /// Update the fee of each priced operation (only contract owner can update it)
+        #[ink(message)]
+        pub fn set_fee_schedule(&mut self, fees: FeeSchedule) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can update the fee schedule
+            if caller != self.owner {
+                return Err("Only the owner can update the fee schedule");
+            }
+
+            self.fees = fees;
+            self.record_admin_action(caller, AdminAction::FeeScheduleSet);
+            Ok(())
+        }
+
+        /// Set the registration period of an alias, 0 disabling aliases (only contract owner can set it)
+        ///
+        /// Existing registrations keep their expiry; the new period applies from the next registration or renewal.
+        #[ink(message)]
+        pub fn set_alias_period(&mut self, alias_period: Timestamp) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can set the alias period
+            if caller != self.owner {
+                return Err("Only the owner can set the alias period");
+            }
+
+            self.alias_period = alias_period;
+            self.record_admin_action(caller, AdminAction::AliasPeriodSet(alias_period));
+            Ok(())
+        }
+
+        /// Publish a new terms-of-service version (only contract owner can publish terms)
+        ///
+        /// With `require_reacceptance`, holders who accepted an older version must accept this one before renewing.
+        #[ink(message)]
+        pub fn publish_terms(&mut self, terms_hash: [u8; 32], require_reacceptance: bool) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can publish terms
+            if caller != self.owner {
+                return Err("Only the owner can publish terms");
+            }
+
+            let version = self.terms_version + 1;
+            self.terms.insert(version, terms_hash);
+            self.terms_version = version;
+            if require_reacceptance {
+                self.min_terms_version = version;
+            }
+            self.env().emit_event(TermsPublished {
+                schema_version: EVENT_SCHEMA_VERSION,
+                version,
+                terms_hash,
+                require_reacceptance,
+            });
+            self.record_admin_action(caller, AdminAction::TermsPublished(version));
+            Ok(())
+        }
+
+        /// Halt submissions, updates and verifier decisions (only contract owner can pause)
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can pause the contract
+            if caller != self.owner {
+                return Err("Only the owner can pause the contract");
+            }
+
+            self.set_paused(true);
+            self.record_admin_action(caller, AdminAction::Paused);
+            Ok(())
+        }
+
+        /// Resume submissions, updates and verifier decisions (only contract owner can unpause)
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can unpause the contract
+            if caller != self.owner {
+                return Err("Only the owner can unpause the contract");
+            }
+
+            self.set_paused(false);
+            self.record_admin_action(caller, AdminAction::Unpaused);
+            Ok(())
+        }
+
+        /// Switch off the `MESSAGE_*` groups in `disabled_messages` and back on every other group (only contract owner can set them)
+        #[ink(message)]
+        pub fn set_disabled_messages(&mut self, disabled_messages: u32) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can trip circuit breakers
+            if caller != self.owner {
+                return Err("Only the owner can set circuit breakers");
+            }
+            if disabled_messages & !MESSAGE_ALL != 0 {
+                return Err("Unknown message group");
+            }
+
+            self.disabled_messages = disabled_messages;
+            self.env().emit_event(CircuitBreakersChanged {
+                schema_version: EVENT_SCHEMA_VERSION,
+                by: caller,
+                disabled_messages,
+            });
+            self.record_admin_action(caller, AdminAction::CircuitBreakersSet(disabled_messages));
+            Ok(())
+        }
+
+        /// Report every verified identity as suspended until the freeze is lifted (only contract owner can freeze)
+        ///
+        /// Unlike `pause`, which halts writes, the freeze makes `is_verified` and every other read report no verified
+        /// identities, for use while a verifier-key compromise is investigated. Stored records are left untouched.
+        #[ink(message)]
+        pub fn emergency_freeze(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can freeze the registry
+            if caller != self.owner {
+                return Err("Only the owner can freeze the registry");
+            }
+            if self.frozen {
+                return Err("Registry already frozen");
+            }
+
+            self.frozen = true;
+            self.env().emit_event(EmergencyFrozen {
+                schema_version: EVENT_SCHEMA_VERSION,
+                by: caller,
+            });
+            self.record_admin_action(caller, AdminAction::Frozen);
+            Ok(())
+        }
+
+        /// Lift an emergency freeze, restoring verified statuses (only contract owner can unfreeze)
+        #[ink(message)]
+        pub fn lift_freeze(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can unfreeze the registry
+            if caller != self.owner {
+                return Err("Only the owner can unfreeze the registry");
+            }
+            if !self.frozen {
+                return Err("Registry is not frozen");
+            }
+
+            self.frozen = false;
+            self.env().emit_event(EmergencyUnfrozen {
+                schema_version: EVENT_SCHEMA_VERSION,
+                by: caller,
+            });
+            self.record_admin_action(caller, AdminAction::Unfrozen);
+            Ok(())
+        }
+
+        /// Schedule a code upgrade that can be applied once `upgrade_delay` has passed (only contract owner can schedule it)
+        ///
+        /// The new code must keep the current storage layout or declare the next layout version, which it migrates to itself.
+        #[ink(message)]
+        pub fn schedule_upgrade(&mut self, code_hash: [u8; 32], storage_version: u32) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can schedule upgrades
+            if caller != self.owner {
+                return Err("Only the owner can schedule upgrades");
+            }
+            if storage_version != self.storage_version && storage_version != self.storage_version + 1 {
+                return Err("Incompatible storage version");
+            }
+            if self.pending_upgrade.is_some() {
+                return Err("Upgrade already scheduled");
+            }
+
+            let executable_at = self.env().block_timestamp() + self.config.upgrade_delay;
+            self.pending_upgrade = Some(PendingUpgrade {
+                code_hash,
+                storage_version,
+                executable_at,
+            });
+            self.env().emit_event(UpgradeScheduled {
+                schema_version: EVENT_SCHEMA_VERSION,
+                code_hash,
+                storage_version,
+                executable_at,
+            });
+            self.record_admin_action(caller, AdminAction::UpgradeScheduled(code_hash));
+            Ok(())
+        }
+
+        /// Cancel the scheduled code upgrade (only contract owner can cancel it)
+        #[ink(message)]
+        pub fn cancel_upgrade(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can cancel upgrades
+            if caller != self.owner {
+                return Err("Only the owner can cancel upgrades");
+            }
+
+            let upgrade = self.pending_upgrade.take().ok_or("No upgrade scheduled")?;
+            self.env().emit_event(UpgradeCancelled {
+                schema_version: EVENT_SCHEMA_VERSION,
+                code_hash: upgrade.code_hash,
+            });
+            self.record_admin_action(caller, AdminAction::UpgradeCancelled(upgrade.code_hash));
+            Ok(())
+        }
+
+        /// Replace the contract code with the scheduled upgrade once its timelock has passed (only contract owner can apply it)
+        #[ink(message)]
+        pub fn apply_upgrade(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can apply upgrades
+            if caller != self.owner {
+                return Err("Only the owner can apply upgrades");
+            }
+            let upgrade = self.pending_upgrade.ok_or("No upgrade scheduled")?;
+            if self.env().block_timestamp() < upgrade.executable_at {
+                return Err("Upgrade timelock has not passed");
+            }
+
+            // Behind the forwarder the new code is pinned in its logic slot; replacing the forwarder's own code would strand it
+            if self.is_forwarded() {
+                ink_env::set_contract_storage(&Key::from(FORWARDER_LOGIC_KEY), &upgrade.code_hash);
+            } else {
+                ink_env::set_code_hash(&upgrade.code_hash).map_err(|_| "Code hash not found")?;
+            }
+            self.pending_upgrade = None;
+            // A new layout is only recorded once the new code has migrated the records; writes stay halted until then
+            if upgrade.storage_version != self.storage_version {
+                self.set_paused(true);
+            }
+            self.env().emit_event(Upgraded {
+                schema_version: EVENT_SCHEMA_VERSION,
+                code_hash: upgrade.code_hash,
+                storage_version: upgrade.storage_version,
+            });
+            self.record_admin_action(caller, AdminAction::Upgraded(upgrade.code_hash));
+            Ok(())
+        }
+
+        /// Rewrite the identity records of `accounts` stored by an earlier storage version, in chunks (only contract owner can migrate)
+        ///
+        /// Available after an in-place upgrade until `complete_storage_migration()`. Each record is read from its cell
+        /// and matched against the earlier layouts; records already in the current layout are skipped.
+        #[ink(message)]
+        pub fn migrate_storage(&mut self, accounts: Vec<AccountId>) -> Result<u32, &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can migrate storage
+            if caller != self.owner {
+                return Err("Only the owner can migrate storage");
+            }
+            if self.storage_version >= STORAGE_VERSION {
+                return Err("Storage already migrated");
+            }
+            if accounts.len() > MAX_PAGE_SIZE as usize {
+                return Err("Too many records");
+            }
+
+            let mut migrated = 0;
+            for account in accounts {
+                let cell = self.identity_cell(&account);
+                let bytes = match ink_env::get_contract_storage::<RawCell>(&cell).ok().flatten() {
+                    Some(RawCell(bytes)) => bytes,
+                    None => return Err("Identity not found"),
+                };
+                if let Some((identity, key_index)) = upgrade_identity_entry(&bytes)? {
+                    self.status_words.insert(
+                        account,
+                        StatusWord {
+                            status: identity.status,
+                            expires_at: identity.lapses_at().unwrap_or(0),
+                        },
+                    );
+                    ink_env::set_contract_storage(&cell, &(&identity, key_index));
+                    migrated += 1;
+                }
+            }
+            self.record_admin_action(caller, AdminAction::RecordsMigrated(migrated));
+            Ok(migrated)
+        }
+
+        /// Record the storage as migrated to this code's layout once every record has been rewritten (only contract owner can call this)
+        #[ink(message)]
+        pub fn complete_storage_migration(&mut self) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can complete the migration
+            if caller != self.owner {
+                return Err("Only the owner can migrate storage");
+            }
+            if self.storage_version >= STORAGE_VERSION {
+                return Err("Storage already migrated");
+            }
+
+            let from = self.storage_version;
+            self.storage_version = STORAGE_VERSION;
+            self.env().emit_event(StorageMigrated {
+                schema_version: EVENT_SCHEMA_VERSION,
+                from,
+                to: STORAGE_VERSION,
+            });
+            self.record_admin_action(caller, AdminAction::StorageMigrated(STORAGE_VERSION));
+            Ok(())
+        }
+
+        /// Apply several administrative operations in one call (only contract owner can call this)
+        ///
+        /// Every operation is infallible once ownership is checked, so the batch is applied atomically.
+        #[ink(message)]
+        pub fn multicall(&mut self, ops: Vec<AdminOp>) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can apply admin operations
+            if caller != self.owner {
+                return Err("Only the owner can apply admin operations");
+            }
+
+            for op in ops {
+                let action = match op {
+                    AdminOp::SetConfig(config) => {
+                        self.config = config;
+                        AdminAction::ConfigChanged
+                    }
+                    AdminOp::AddVerifier(account) => {
+                        self.verifiers.insert(account);
+                        AdminAction::VerifierAdded(account)
+                    }
+                    AdminOp::RemoveVerifier(account) => {
+                        self.verifiers.take(&account);
+                        AdminAction::VerifierRemoved(account)
+                    }
+                    AdminOp::AddTreasurer(account) => {
+                        self.treasurers.insert(account);
+                        AdminAction::TreasurerAdded(account)
+                    }
+                    AdminOp::RemoveTreasurer(account) => {
+                        self.treasurers.take(&account);
+                        AdminAction::TreasurerRemoved(account)
+                    }
+                    AdminOp::AddRelayer(account) => {
+                        self.relayers.insert(account);
+                        AdminAction::RelayerAdded(account)
+                    }
+                    AdminOp::RemoveRelayer(account) => {
+                        self.relayers.take(&account);
+                        AdminAction::RelayerRemoved(account)
+                    }
+                    AdminOp::AddOracle(account) => {
+                        self.oracles.insert(account);
+                        AdminAction::OracleAdded(account)
+                    }
+                    AdminOp::RemoveOracle(account) => {
+                        self.oracles.take(&account);
+                        AdminAction::OracleRemoved(account)
+                    }
+                    AdminOp::AddRelyingParty(account) => {
+                        self.relying_parties.insert(account);
+                        AdminAction::RelyingPartyAdded(account)
+                    }
+                    AdminOp::RemoveRelyingParty(account) => {
+                        self.relying_parties.take(&account);
+                        AdminAction::RelyingPartyRemoved(account)
+                    }
+                    AdminOp::Pause => {
+                        self.set_paused(true);
+                        AdminAction::Paused
+                    }
+                    AdminOp::Unpause => {
+                        self.set_paused(false);
+                        AdminAction::Unpaused
+                    }
+                };
+                self.record_admin_action(caller, action);
+            }
+            Ok(())
+        }
+
+        /// Clear the failed-attempt count of an account and lift its lockout (only contract owner can call this)
+        #[ink(message)]
+        pub fn clear_lockout(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can clear lockouts
+            if caller != self.owner {
+                return Err("Only the owner can clear lockouts");
+            }
+
+            if let Some(identity) = self.identities.get_mut(&account) {
+                identity.failed_attempts = 0;
+            }
+            if self.locked_accounts.take(&account).is_some() {
+                self.env().emit_event(AccountUnlocked {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                });
+            }
+            self.record_admin_action(caller, AdminAction::LockoutCleared(account));
+            Ok(())
+        }
+
+        /// Import SCALE-encoded `(AccountId, Identity)` records exported from another deployment (only contract owner can import)
+        #[ink(message)]
+        pub fn import_chunk(&mut self, records: Vec<Vec<u8>>) -> Result<u32, &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can import records
+            if caller != self.owner {
+                return Err("Only the owner can import identities");
+            }
+            if records.len() > MAX_PAGE_SIZE as usize {
+                return Err("Too many records");
+            }
+
+            let mut imported = 0;
+            for record in records {
+                let (account, mut identity) =
+                    <(AccountId, Identity) as scale::Decode>::decode(&mut &record[..]).map_err(|_| "Invalid identity record")?;
+                if self.identities.get(&account).is_some() {
+                    return Err("Identity already exists");
+                }
+
+                // Held fees and deposits stay with the source deployment
+                identity.fee_paid = 0;
+                identity.deposit = 0;
+                // Certificate ids are only meaningful on the deployment that issued them
+                identity.certificate_id = None;
+                self.insert_imported(account, identity);
+                imported += 1;
+            }
+            self.record_admin_action(caller, AdminAction::RecordsImported(imported));
+            Ok(imported)
+        }
+
+        /// Rewrite the v1 records of `accounts` into the current layout, in chunks (only contract owner can migrate)
+        ///
+        /// Each record is read from the cell the v1 `identities` map stored it in, which is the cell the current map
+        /// uses, and rewritten there, so only data written by the v1 code is migrated. Verified records keep their
+        /// verifier, with the verification dated to the migration; fields v1 did not track start at their defaults.
+        #[ink(message)]
+        pub fn migrate_from_v1(&mut self, accounts: Vec<AccountId>) -> Result<u32, &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can migrate records
+            if caller != self.owner {
+                return Err("Only the owner can migrate identities");
+            }
+            if accounts.len() > MAX_PAGE_SIZE as usize {
+                return Err("Too many records");
+            }
+
+            let now = self.env().block_timestamp();
+            let mut migrated = 0;
+            for account in accounts {
+                // Map entries are stored with the index of their key in the map's key stash
+                let cell = self.identity_cell(&account);
+                if ink_env::get_contract_storage::<(Identity, u32)>(&cell).ok().flatten().is_some() {
+                    return Err("Identity already migrated");
+                }
+                let (v1, key_index) = ink_env::get_contract_storage::<(IdentityV1, u32)>(&cell)
+                    .ok()
+                    .flatten()
+                    .ok_or("No v1 record")?;
+
+                let name = to_fixed_bytes::<MAX_NAME_LENGTH>(&v1.name).ok_or("Name too long")?;
+                let document_id = to_fixed_bytes::<MAX_DOCUMENT_ID_LENGTH>(&v1.document_id).ok_or("Document id too long")?;
+                let document_hash = self.env().hash_bytes::<ink_env::hash::Blake2x256>(v1.document_id.as_bytes());
+                let status = if v1.is_verified { Status::Verified } else { Status::Pending };
+                let mut identity = Identity {
+                    name,
+                    age: v1.age,
+                    document_id,
+                    document_hash,
+                    proofs: Vec::new(),
+                    status,
+                    verifier: v1.verifier,
+                    fee_paid: 0,
+                    deposit: 0,
+                    relayer: None,
+                    verified_at: if v1.is_verified { now } else { 0 },
+                    expires_at: None,
+                    credential_type: DEFAULT_CREDENTIAL_TYPE,
+                    attempt_count: 1,
+                    reject_reason: None,
+                    failed_attempts: 0,
+                    assigned_verifier: None,
+                    assignment_deadline: None,
+                    assigned_at: None,
+                    submitted_at: now,
+                    review_time: 0,
+                    notes_hash: None,
+                    priority_fee: 0,
+                    certificate_id: None,
+                    erased_at: None,
+                    terms_version: 0,
+                    terms_accepted_at: 0,
+                    jurisdiction: [0; 2],
+                    status_changed_at: now,
+                    document_kind: DocumentKind::Other,
+                    document_expires_at: None,
+                };
+                identity.set_proof_hash(v1.proof_hash, now);
+                if let (true, Some(entry)) = (v1.is_verified, identity.proofs.first_mut()) {
+                    entry.attested_by = v1.verifier;
+                }
+                ink_env::set_contract_storage(&cell, &(&identity, key_index));
+                self.insert_imported(account, identity);
+                self.record_history(account, status, caller, "Migrated from v1");
+                migrated += 1;
+            }
+            self.record_admin_action(caller, AdminAction::RecordsMigrated(migrated));
+            Ok(migrated)
+        }
+
+        /// Register credential type `id`, requiring the `DATA_CATEGORY_*` attributes in `required_attributes` (only contract owner can register)
+        ///
+        /// Submissions and updates must satisfy `schema`. Verifications of the type last `validity`, or the
+        /// configured `verification_validity` when it is 0.
+        #[ink(message)]
+        pub fn register_credential_type(
+            &mut self,
+            id: u32,
+            name_hash: [u8; 32],
+            required_attributes: u32,
+            schema: CredentialSchema,
+            validity: Timestamp,
+        ) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can register credential types
+            if caller != self.owner {
+                return Err("Only the owner can register credential types");
+            }
+            if self.credential_types.contains_key(&id) {
+                return Err("Credential type already registered");
+            }
+            if required_attributes & !DATA_CATEGORY_ALL != 0 {
+                return Err("Unknown attribute");
+            }
+            if schema.max_age > 0 && schema.max_age < schema.min_age {
+                return Err("Invalid age bounds");
+            }
+
+            self.credential_types.insert(
+                id,
+                CredentialType {
+                    name_hash,
+                    required_attributes,
+                    schema,
+                    validity,
+                    active: true,
+                },
+            );
+            self.env().emit_event(CredentialTypeRegistered {
+                schema_version: EVENT_SCHEMA_VERSION,
+                id,
+                name_hash,
+                required_attributes,
+                validity,
+            });
+            self.record_admin_action(caller, AdminAction::CredentialTypeRegistered(id));
+            Ok(())
+        }
+
+        /// Stop accepting submissions for credential type `id`; existing verifications are kept (only contract owner can deactivate)
+        #[ink(message)]
+        pub fn deactivate_credential_type(&mut self, id: u32) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can deactivate credential types
+            if caller != self.owner {
+                return Err("Only the owner can deactivate credential types");
+            }
+            let credential = self.credential_types.get_mut(&id).ok_or("Credential type not found")?;
+            if !credential.active {
+                return Err("Credential type already inactive");
+            }
+
+            credential.active = false;
+            self.env().emit_event(CredentialTypeDeactivated {
+                schema_version: EVENT_SCHEMA_VERSION,
+                id,
+            });
+            self.record_admin_action(caller, AdminAction::CredentialTypeDeactivated(id));
+            Ok(())
+        }
+
+        /// Enter migration mode towards the deployment `target`, pausing this one (only contract owner can start a migration)
+        ///
+        /// `target` must have accepted this deployment with `accept_migrations_from()`. Records stay readable here.
+        #[ink(message)]
+        pub fn start_migration(&mut self, target: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can start a migration
+            if caller != self.owner {
+                return Err("Only the owner can start a migration");
+            }
+            if target == self.env().account_id() {
+                return Err("Cannot migrate to this contract");
+            }
+
+            self.migration_target = Some(target);
+            self.set_paused(true);
+            self.env().emit_event(MigrationStarted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                target,
+            });
+            self.record_admin_action(caller, AdminAction::MigrationStarted(target));
+            Ok(())
+        }
+
+        /// Push the records of `accounts` to the migration target, keeping verifier attribution and timestamps (only contract owner can migrate)
+        #[ink(message)]
+        pub fn migrate_records(&mut self, accounts: Vec<AccountId>) -> Result<u32, &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure only the owner can migrate records
+            if caller != self.owner {
+                return Err("Only the owner can migrate identities");
+            }
+            let target = self.migration_target.ok_or("Not in migration mode")?;
+            if accounts.len() > MAX_PAGE_SIZE as usize {
+                return Err("Too many records");
+            }
+
+            let mut migrated = 0;
+            for account in accounts {
+                let identity = self.identities.get(&account).cloned().ok_or("Identity not found")?;
+                self.set_entered(true);
+                let result = build_call::<Environment>()
+                    .call_type(Call::new().callee(target))
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(RECEIVE_MIGRATED_RECORD_SELECTOR))
+                            .push_arg(account)
+                            .push_arg(identity),
+                    )
+                    .returns::<Result<(), String>>()
+                    .fire();
+                self.set_entered(false);
+                match result {
+                    Ok(Ok(())) => {}
+                    Ok(Err(_)) => return Err("Record rejected by migration target"),
+                    Err(_) => return Err("Migration call failed"),
+                }
+                self.env().emit_event(RecordMigrated {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    target,
+                });
+                migrated += 1;
+            }
+            self.record_admin_action(caller, AdminAction::RecordsMigrated(migrated));
+            Ok(migrated)
+        }
+
+        /// Allow the deployment `source` to push migrated records here, or stop accepting them with `None` (only contract owner can set this)
+        #[ink(message)]
+        pub fn accept_migrations_from(&mut self, source: Option<AccountId>) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can set the migration source
+            if caller != self.owner {
+                return Err("Only the owner can set the migration source");
+            }
+
+            self.migration_source = source;
+            self.record_admin_action(caller, AdminAction::MigrationSourceSet(source));
+            Ok(())
+        }
+
+        /// Store a record pushed by the accepted migration source, keeping its verifier and timestamps
+        #[ink(message)]
+        pub fn receive_migrated_record(&mut self, account: AccountId, identity: Identity) -> Result<(), &'static str> {
+            let mut identity = identity;
+            let caller = self.env().caller();
+            // Ensure only the accepted migration source can push records
+            if Some(caller) != self.migration_source {
+                return Err("Only the migration source can push records");
+            }
+            if self.identities.get(&account).is_some() {
+                return Err("Identity already exists");
+            }
+
+            // Held fees and deposits stay with the source deployment
+            identity.fee_paid = 0;
+            identity.deposit = 0;
+            // Certificate ids are only meaningful on the deployment that issued them
+            identity.certificate_id = None;
+            self.insert_imported(account, identity);
+            self.env().emit_event(MigratedRecordReceived {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                source: caller,
+            });
+            Ok(())
+        }
+
+        /// Get the review-notes hash attached to an identity's decision (only the owner, verifiers and relying parties)
+        #[ink(message)]
+        pub fn get_attestation_notes(&self, account: AccountId) -> Option<[u8; 32]> {
+            if !self.is_trusted_caller(self.env().caller()) {
+                return None;
+            }
+            self.identities.get(&account).and_then(|identity| identity.notes_hash)
+        }
+
+        /// Get the co-signer designated by a holder, if any
+        #[ink(message)]
+        pub fn get_cosigner(&self, account: AccountId) -> Option<AccountId> {
+            self.cosigners.get(&account).copied()
+        }
+
+        /// Get the block at which an open approval can be finalized, if any
+        #[ink(message)]
+        pub fn get_approval(&self, account: AccountId) -> Option<BlockNumber> {
+            self.approvals.get(&account).copied()
+        }
+
+        /// Get the pending renewal of an identity, if any
+        #[ink(message)]
+        pub fn get_renewal(&self, account: AccountId) -> Option<Renewal> {
+            self.renewals.get(&account).cloned()
+        }
+
+        /// Get the open dispute against an identity, if any
+        #[ink(message)]
+        pub fn get_dispute(&self, account: AccountId) -> Option<Dispute> {
+            self.disputes.get(&account).cloned()
+        }
+
+        /// Page through the admin audit log from log index `offset`, oldest first
+        ///
+        /// Only the latest `MAX_AUDIT_LOG` entries are retained; an offset below the retained range starts at the oldest retained entry.
+        #[ink(message)]
+        pub fn get_audit_log(&self, offset: u32, limit: u32) -> Vec<AuditEntry> {
+            let start = offset.max(self.audit_count.saturating_sub(MAX_AUDIT_LOG));
+            let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.audit_count);
+            (start..end).filter_map(|index| self.audit_log.get(&index).copied()).collect()
+        }
+
+        /// Get the number of admin actions ever recorded
+        #[ink(message)]
+        pub fn get_audit_count(&self) -> u32 {
+            self.audit_count
+        }
+
+        /// Resolve a certificate id to the attestation it records
+        ///
+        /// A certificate stays resolvable after it is superseded; it is current while it matches the identity's
+        /// `certificate_id` and the identity is verified. The verifier is withheld unless the caller is trusted or subscribed.
+        #[ink(message)]
+        pub fn get_certificate(&self, id: u64) -> Option<Certificate> {
+            let mut certificate = self.certificates.get(&id).copied()?;
+            let caller = self.env().caller();
+            if !self.is_trusted_caller(caller) && !self.is_subscribed(caller) {
+                certificate.verifier = None;
+            }
+            Some(certificate)
+        }
+
+        /// Get a verifier's decision count, cumulative review time and SLA breaches
+        #[ink(message)]
+        pub fn get_verifier_stats(&self, verifier: AccountId) -> VerifierStats {
+            self.verifier_stats.get(&verifier).copied().unwrap_or_default()
+        }
+
+        /// Get the number of claimed requests a verifier let time out
+        #[ink(message)]
+        pub fn get_verifier_timeouts(&self, verifier: AccountId) -> u32 {
+            self.verifier_timeouts.get(&verifier).copied().unwrap_or(0)
+        }
+
+        /// Get a verifier's reputation: disputes dismissed minus disputes upheld against their decisions, less SLA breaches
+        #[ink(message)]
+        pub fn get_verifier_reputation(&self, verifier: AccountId) -> i32 {
+            self.verifier_reputation.get(&verifier).copied().unwrap_or(0)
+        }
+
+        /// Get a holder's soulbound attestation score, built from successful verifications, renewals and a record free of upheld challenges
+        ///
+        /// The score reflects the account's whole history and never moves to another account; combine it with
+        /// `is_verified` to require a current verification as well.
+        #[ink(message)]
+        pub fn get_reputation(&self, account: AccountId) -> u32 {
+            let verifications = self.verification_counts.get(&account).copied().unwrap_or(0);
+            let renewals = self.renewal_counts.get(&account).copied().unwrap_or(0);
+            let mut score = verifications
+                .saturating_mul(REPUTATION_PER_VERIFICATION)
+                .saturating_add(renewals.saturating_mul(REPUTATION_PER_RENEWAL));
+            if verifications > 0 && self.dispute_counts.get(&account).copied().unwrap_or(0) == 0 {
+                score = score.saturating_add(REPUTATION_CLEAN_RECORD_BONUS);
+            }
+            score
+        }
+
+        /// Get the highest KYC tier attested for a currently verified identity
+        #[ink(message)]
+        pub fn get_kyc_level(&self, account: AccountId) -> Option<KycLevel> {
+            if !self.is_verified(account) {
+                return None;
+            }
+            self.kyc_levels.get(&account).copied()
+        }
+
+        /// Get the verifier that attested a KYC tier of an identity
+        #[ink(message)]
+        pub fn get_kyc_attestation(&self, account: AccountId, level: KycLevel) -> Option<AccountId> {
+            self.kyc_attestations.get(&(account, level)).copied()
+        }
+
+        /// Get the highest KYC tier a verifier may attest
+        #[ink(message)]
+        pub fn get_verifier_tier(&self, verifier: AccountId) -> KycLevel {
+            self.verifier_tiers.get(&verifier).copied().unwrap_or(KycLevel::Basic)
+        }
+
+        /// Check if an account is locked out after too many failed attempts
+        #[ink(message)]
+        pub fn is_locked(&self, account: AccountId) -> bool {
+            self.locked_accounts.contains(&account)
+        }
+
+        /// Check if the contract is paused
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Get the `MESSAGE_*` groups switched off by their circuit breaker
+        #[ink(message)]
+        pub fn get_disabled_messages(&self) -> u32 {
+            self.disabled_messages
+        }
+
+        /// Get the scheduled code upgrade, if any
+        #[ink(message)]
+        pub fn get_pending_upgrade(&self) -> Option<PendingUpgrade> {
+            self.pending_upgrade
+        }
+
+        /// Get the version of the storage layout currently in use
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u32 {
+            self.storage_version
+        }
+
+        /// Get the deployment records are being migrated to, if in migration mode
+        #[ink(message)]
+        pub fn get_migration_target(&self) -> Option<AccountId> {
+            self.migration_target
+        }
+
+        /// Get the deployment allowed to push migrated records here, if any
+        #[ink(message)]
+        pub fn get_migration_source(&self) -> Option<AccountId> {
+            self.migration_source
+        }
+
+        /// Check if an emergency freeze is in effect
+        #[ink(message)]
+        pub fn is_frozen(&self) -> bool {
+            self.frozen
+        }
+
+        /// Get the current contract configuration
+        #[ink(message)]
+        pub fn get_config(&self) -> Config {
+            self.config.clone()
+        }
+
+        /// Get the fee of each priced operation
+        #[ink(message)]
+        pub fn get_fee_schedule(&self) -> FeeSchedule {
+            self.fees
+        }
+
+        /// Get the registration period of an alias (0 when aliases are disabled)
+        #[ink(message)]
+        pub fn get_alias_period(&self) -> Timestamp {
+            self.alias_period
+        }
+
+        /// Export a page of SCALE-encoded `(AccountId, Identity)` records for migration (only contract owner can export)
+        #[ink(message)]
+        pub fn export_chunk(&self, offset: u32, limit: u32) -> Result<Vec<Vec<u8>>, &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can export records
+            if caller != self.owner {
+                return Err("Only the owner can export identities");
+            }
+
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.accounts.len());
+            Ok((offset..end)
+                .filter_map(|index| self.accounts.get(index).copied())
+                .filter_map(|account| {
+                    self.identities
+                        .get(&account)
+                        .map(|identity| scale::Encode::encode(&(account, identity)))
+                })
+                .collect())
+        }
+
+        /// Add a compliance officer (only contract owner can add compliance officers)
+        #[ink(message)]
+        pub fn add_compliance_officer(&mut self, officer: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can add compliance officers
+            if caller != self.owner {
+                return Err("Only the owner can add compliance officers");
+            }
+
+            self.compliance_officers.insert(officer);
+            self.record_admin_action(caller, AdminAction::ComplianceOfficerAdded(officer));
+            Ok(())
+        }
+
+        /// Remove a compliance officer (only contract owner can remove compliance officers)
+        #[ink(message)]
+        pub fn remove_compliance_officer(&mut self, officer: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can remove compliance officers
+            if caller != self.owner {
+                return Err("Only the owner can remove compliance officers");
+            }
+
+            self.compliance_officers.take(&officer);
+            self.record_admin_action(caller, AdminAction::ComplianceOfficerRemoved(officer));
+            Ok(())
+        }
+
+        /// Place a legal hold on an identity, blocking its erasure and purge (only compliance officers can call this)
+        #[ink(message)]
+        pub fn place_legal_hold(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure the caller is a compliance officer
+            if !self.compliance_officers.contains(&caller) {
+                return Err("Only compliance officers can place legal holds");
+            }
+            if !self.identities.contains_key(&account) {
+                return Err("Identity not found");
+            }
+            if self.legal_holds.contains_key(&account) {
+                return Err("Identity already under legal hold");
+            }
+
+            self.legal_holds.insert(account, caller);
+            self.env().emit_event(LegalHoldPlaced {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                officer: caller,
+            });
+            self.record_admin_action(caller, AdminAction::LegalHoldPlaced(account));
+            Ok(())
+        }
+
+        /// Lift the legal hold on an identity (only compliance officers can call this)
+        #[ink(message)]
+        pub fn lift_legal_hold(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure the caller is a compliance officer
+            if !self.compliance_officers.contains(&caller) {
+                return Err("Only compliance officers can lift legal holds");
+            }
+            if self.legal_holds.take(&account).is_none() {
+                return Err("Identity is not under legal hold");
+            }
+
+            self.env().emit_event(LegalHoldLifted {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                officer: caller,
+            });
+            self.record_admin_action(caller, AdminAction::LegalHoldLifted(account));
+            Ok(())
+        }
+
+        /// Add a new treasurer (only contract owner can add treasurers)
+        #[ink(message)]
+        pub fn add_treasurer(&mut self, treasurer: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can add treasurers
+            if caller != self.owner {
+                return Err("Only the owner can add treasurers");
+            }
+
+            self.treasurers.insert(treasurer);
+            self.record_admin_action(caller, AdminAction::TreasurerAdded(treasurer));
+            Ok(())
+        }
+
+        /// Remove a treasurer (only contract owner can remove treasurers)
+        #[ink(message)]
+        pub fn remove_treasurer(&mut self, treasurer: AccountId) -> Result<(), &'static str> {
+            let caller = self.env().caller();
+            // Ensure only the owner can remove treasurers
+            if caller != self.owner {
+                return Err("Only the owner can remove treasurers");
+            }
+
+            self.treasurers.take(&treasurer);
+            self.record_admin_action(caller, AdminAction::TreasurerRemoved(treasurer));
+            Ok(())
+        }
+
+        /// Withdraw treasury funds to an account (only owner or treasurers can call this)
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance, to: AccountId) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is the owner or a treasurer
+            if caller != self.owner && !self.treasurers.contains(&caller) {
+                return Err("Only the owner or treasurers can withdraw");
+            }
+
+            // Ensure the treasury holds enough funds
+            if amount > self.treasury_balance() {
+                return Err("Insufficient treasury balance");
+            }
+
+            self.treasury.total_withdrawn += amount;
+            self.env().transfer(to, amount).map_err(|_| "Transfer failed")?;
+
+            // Emit an event for the withdrawal
+            self.env().emit_event(TreasuryWithdrawal {
+                schema_version: EVENT_SCHEMA_VERSION,
+                to,
+                caller,
+                amount,
+            });
+
+            self.record_admin_action(caller, AdminAction::Withdrawal(to, amount));
+            Ok(())
+        }
+
+        /// Withdraw collected fee tokens to an account (only owner or treasurers can call this)
+        #[ink(message)]
+        pub fn withdraw_token(&mut self, amount: Balance, to: AccountId) -> Result<(), &'static str> {
+            self.ensure_not_entered()?;
+            let caller = self.env().caller();
+            // Ensure the caller is the owner or a treasurer
+            if caller != self.owner && !self.treasurers.contains(&caller) {
+                return Err("Only the owner or treasurers can withdraw");
+            }
+            let token = self.config.fee_token.ok_or("Fee token not configured")?;
+
+            // Ensure the treasury holds enough tokens
+            if amount > self.treasury.token_collected - self.treasury.token_withdrawn {
+                return Err("Insufficient treasury balance");
+            }
+
+            self.treasury.token_withdrawn += amount;
+            self.set_entered(true);
+            let result = build_call::<Environment>()
+                .call_type(Call::new().callee(token))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(PSP22_TRANSFER_SELECTOR))
+                        .push_arg(to)
+                        .push_arg(amount)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), Psp22Error>>()
+                .fire();
+            self.set_entered(false);
+            result.map_err(|_| "Token call failed")?.map_err(|_| "Token transfer failed")?;
+
+            self.env().emit_event(TreasuryTokenWithdrawal {
+                schema_version: EVENT_SCHEMA_VERSION,
+                to,
+                caller,
+                token,
+                amount,
+            });
+
+            self.record_admin_action(caller, AdminAction::TokenWithdrawal(to, amount));
+            Ok(())
+        }
+
+        /// Get the treasury accounting totals
+        #[ink(message)]
+        pub fn get_treasury(&self) -> Treasury {
+            self.treasury.clone()
+        }
+
+        /// Get the funds currently available for withdrawal from the treasury
+        #[ink(message)]
+        pub fn treasury_balance(&self) -> Balance {
+            self.treasury.total_collected.saturating_sub(self.treasury.total_withdrawn)
+        }
+
+        /// Check if an account is a compliance officer
+        #[ink(message)]
+        pub fn is_compliance_officer(&self, account: AccountId) -> bool {
+            self.compliance_officers.contains(&account)
+        }
+
+        /// Get the compliance officer holding a legal hold on an identity, if any
+        #[ink(message)]
+        pub fn get_legal_hold(&self, account: AccountId) -> Option<AccountId> {
+            self.legal_holds.get(&account).copied()
+        }
+
+        /// Check if an account is a treasurer
+        #[ink(message)]
+        pub fn is_treasurer(&self, account: AccountId) -> bool {
+            self.treasurers.contains(&account)
+        }
+
+        /// Check if an account holds an active relying-party subscription
+        #[ink(message)]
+        pub fn is_subscribed(&self, account: AccountId) -> bool {
+            self.subscriptions
+                .get(&account)
+                .map_or(false, |expires_at| *expires_at > self.env().block_timestamp())
+        }
+
+        /// Get the stored identity for a specific account (only the holder, owner, verifiers and relying parties)
+        #[ink(message)]
+        pub fn get_identity(&self, account: AccountId) -> Option<Identity> {
+            let caller = self.env().caller();
+            let identity = self.identities.get(&account)?;
+            if caller == account || self.is_trusted_caller(caller) {
+                return Some(identity.clone());
+            }
+
+            // Holder-granted readers only see the data categories they were granted
+            let grant = self.read_grants.get(&(account, caller))?;
+            if grant.expires_at <= self.env().block_timestamp() {
+                return None;
+            }
+            let mut identity = identity.clone();
+            if grant.data_categories & DATA_CATEGORY_NAME == 0 {
+                identity.name = [0; MAX_NAME_LENGTH];
+            }
+            if grant.data_categories & DATA_CATEGORY_AGE == 0 {
+                identity.age = 0;
+            }
+            if grant.data_categories & DATA_CATEGORY_DOCUMENT == 0 {
+                identity.document_id = [0; MAX_DOCUMENT_ID_LENGTH];
+            }
+            Some(identity)
+        }
+
+        /// Get the read grant a holder gave to `grantee`, if any
+        #[ink(message)]
+        pub fn get_read_grant(&self, holder: AccountId, grantee: AccountId) -> Option<ReadGrant> {
+            self.read_grants.get(&(holder, grantee)).copied()
+        }
+
+        /// Get a registered credential type
+        #[ink(message)]
+        pub fn get_credential_type(&self, id: u32) -> Option<CredentialType> {
+            self.credential_types.get(&id).copied()
+        }
+
+        /// Get the submission, verification and revocation counts for a credential type
+        #[ink(message)]
+        pub fn get_credential_stats(&self, credential_type: u32) -> CredentialStats {
+            self.credential_stats.get(&credential_type).copied().unwrap_or_default()
+        }
+
+        /// Check the verification status of several accounts in one call
+        #[ink(message)]
+        pub fn are_verified(&self, accounts: Vec<AccountId>) -> Vec<bool> {
+            accounts.into_iter().map(|account| self.is_verified(account)).collect()
+        }
+
+        /// Get the verification records of several accounts in one call
+        #[ink(message)]
+        pub fn get_verification_records(&self, accounts: Vec<AccountId>) -> Vec<Option<VerificationRecord>> {
+            accounts
+                .into_iter()
+                .map(|account| self.get_verification_record(account))
+                .collect()
+        }
+
+        /// Get a page of an identity's status transitions, oldest first (only the holder, owner, verifiers and relying parties)
+        #[ink(message)]
+        pub fn get_history(&self, account: AccountId, offset: u32, limit: u32) -> Vec<HistoryEntry> {
+            let caller = self.env().caller();
+            if caller != account && !self.is_trusted_caller(caller) {
+                return Vec::new();
+            }
+            let count = self.history_counts.get(&account).copied().unwrap_or(0);
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+            (offset..end)
+                .filter_map(|index| self.history.get(&(account, index)).cloned())
+                .collect()
+        }
+
+        /// Get the current verification-ledger epoch
+        #[ink(message)]
+        pub fn current_epoch(&self) -> u32 {
+            self.env().block_number() / EPOCH_LENGTH
+        }
+
+        /// Get a page of the accounts newly verified or revoked from `from_epoch` through `to_epoch`, in ledger order
+        ///
+        /// Indexers sync incrementally by asking for the epochs since their last sync, paging with `offset`.
+        #[ink(message)]
+        pub fn verified_in_range(&self, from_epoch: u32, to_epoch: u32, offset: u32, limit: u32) -> Vec<LedgerEntry> {
+            if from_epoch > to_epoch {
+                return Vec::new();
+            }
+            let start = self.ledger_lower_bound(from_epoch);
+            let end = self.ledger_lower_bound(to_epoch.saturating_add(1));
+            let first = start.saturating_add(offset).min(end);
+            let last = first.saturating_add(limit.min(MAX_PAGE_SIZE)).min(end);
+            (first..last).filter_map(|index| self.ledger.get(&index).copied()).collect()
+        }
+
+        /// Get the proof entries of an identity (only the holder, owner, verifiers and relying parties)
+        #[ink(message)]
+        pub fn get_proofs(&self, account: AccountId) -> Vec<ProofEntry> {
+            let caller = self.env().caller();
+            if caller != account && !self.is_trusted_caller(caller) {
+                return Vec::new();
+            }
+            self.identities.get(&account).map_or(Vec::new(), |identity| identity.proofs.clone())
+        }
+
+        /// Get an identity's biometric record (only the holder, the owner and the attesting verifier)
+        #[ink(message)]
+        pub fn get_biometric_record(&self, account: AccountId) -> Option<BiometricRecord> {
+            let caller = self.env().caller();
+            let record = self.biometrics.get(&account)?;
+            if caller != account && caller != self.owner && record.liveness_attested_by != Some(caller) {
+                return None;
+            }
+            Some(*record)
+        }
+
+        /// Check whether a liveness check has been attested for an identity's current biometric template
+        #[ink(message)]
+        pub fn has_liveness(&self, account: AccountId) -> bool {
+            self.biometrics.get(&account).map_or(false, |record| record.liveness_attested_by.is_some())
+        }
+
+        /// Resolve an alias to its owner, if the alias is unexpired and the owner is currently verified
+        #[ink(message)]
+        pub fn resolve_alias(&self, name_hash: [u8; 32]) -> Option<AccountId> {
+            let alias = self.aliases.get(&name_hash)?;
+            if alias.expires_at <= self.env().block_timestamp() || !self.is_verified(alias.owner) {
+                return None;
+            }
+            Some(alias.owner)
+        }
+
+        /// Get an alias registration, including expired ones that have not been re-registered
+        #[ink(message)]
+        pub fn get_alias(&self, name_hash: [u8; 32]) -> Option<Alias> {
+            self.aliases.get(&name_hash).copied()
+        }
+
+        /// Get the name hash of the unexpired alias an account owns (reverse resolution)
+        #[ink(message)]
+        pub fn reverse_resolve(&self, account: AccountId) -> Option<[u8; 32]> {
+            let name_hash = self.alias_of.get(&account).copied()?;
+            let alias = self.aliases.get(&name_hash)?;
+            if alias.expires_at <= self.env().block_timestamp() {
+                return None;
+            }
+            Some(name_hash)
+        }
+
+        /// Get the `NOTIFY_*` flags of an account
+        #[ink(message)]
+        pub fn get_notification_flags(&self, account: AccountId) -> u32 {
+            self.notification_flags.get(&account).copied().unwrap_or(NOTIFY_ALL)
+        }
+
+        /// Get the field-name hashes an identity has committed to
+        #[ink(message)]
+        pub fn get_committed_fields(&self, account: AccountId) -> Vec<[u8; 32]> {
+            self.committed_fields.get(&account).cloned().unwrap_or_default()
+        }
+
+        /// Get an identity's commitment to one field
+        #[ink(message)]
+        pub fn get_field_commitment(&self, account: AccountId, field: [u8; 32]) -> Option<FieldCommitment> {
+            self.field_commitments.get(&(account, field)).copied()
+        }
+
+        /// Check a value disclosed by the holder against its verified field commitment
+        ///
+        /// The holder shares `value` and `salt` off-chain with a relying party, which checks them here without
+        /// learning any other attribute.
+        #[ink(message)]
+        pub fn check_disclosure(&self, account: AccountId, field: [u8; 32], value: Vec<u8>, salt: [u8; 32]) -> bool {
+            let entry = match self.field_commitments.get(&(account, field)) {
+                Some(entry) if entry.verified_by.is_some() => entry,
+                _ => return false,
+            };
+            let mut preimage = value;
+            preimage.extend_from_slice(&salt);
+            self.env().hash_bytes::<ink_env::hash::Blake2x256>(&preimage) == entry.commitment
+        }
+
+        /// Get the effective status of an identity, reading only its status word
+        ///
+        /// Records stored before status words existed fall back to the full identity until their next transition.
+        #[ink(message)]
+        pub fn get_effective_status(&self, account: AccountId) -> Option<Status> {
+            match self.status_words.get(&account) {
+                Some(word) => Some(self.word_status(word)),
+                None => self.identities.get(&account).map(|identity| self.effective_status(identity)),
+            }
+        }
+
+        /// Get the status, timing and attempt details of an identity in a single call
+        #[ink(message)]
+        pub fn get_status(&self, account: AccountId) -> Option<IdentityStatus> {
+            let identity = self.identities.get(&account)?;
+            let caller = self.env().caller();
+            let privileged = self.is_trusted_caller(caller) || self.is_subscribed(caller);
+            Some(self.status_of(identity, privileged))
+        }
+
+        /// Walk the registry in first-submission order, resuming from the cursor returned by the previous page
+        ///
+        /// Accounts are only ever appended to the registry order, so a cursor stays valid across later submissions.
+        #[ink(message)]
+        pub fn iterate_identities(&self, cursor: Option<u32>, limit: u32) -> IdentityPage {
+            let caller = self.env().caller();
+            let privileged = self.is_trusted_caller(caller) || self.is_subscribed(caller);
+            let start = cursor.unwrap_or(0);
+            let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.accounts.len());
+            let records = (start..end)
+                .filter_map(|index| self.accounts.get(index).copied())
+                .filter_map(|account| {
+                    self.identities
+                        .get(&account)
+                        .map(|identity| (account, self.status_of(identity, privileged)))
+                })
+                .collect();
+            IdentityPage {
+                records,
+                next_cursor: if end < self.accounts.len() { Some(end) } else { None },
+            }
+        }
+
+        /// Check if an account is an allowlisted relying party
+        #[ink(message)]
+        pub fn is_relying_party(&self, account: AccountId) -> bool {
+            self.relying_parties.contains(&account)
+        }
+
+        /// Get the escrowed bounty for a specific account
+        #[ink(message)]
+        pub fn get_bounty(&self, account: AccountId) -> Option<Bounty> {
+            self.bounties.get(&account).cloned()
+        }
+
+        /// Get the claimable earnings of a verifier
+        #[ink(message)]
+        pub fn get_earnings(&self, verifier: AccountId) -> Balance {
+            self.earnings.get(&verifier).copied().unwrap_or(0)
+        }
+
+        /// Check if an account is a registered verifier
+        #[ink(message)]
+        pub fn is_verifier(&self, account: AccountId) -> bool {
+            self.verifiers.contains(&account)
+        }
+
+        /// Check if an account is a registered relayer
+        #[ink(message)]
+        pub fn is_relayer(&self, account: AccountId) -> bool {
+            self.relayers.contains(&account)
+        }
+
+        /// Get the submission fee under the current pending-queue congestion
+        #[ink(message)]
+        pub fn current_submission_fee(&self) -> Balance {
+            let base = self.fees.fee(FeeKind::Submission);
+            let excess = self.pending.len().saturating_sub(self.config.congestion_threshold) as Balance;
+            let mut multiplier_bps =
+                BASIS_POINTS.saturating_add(excess.saturating_mul(self.config.congestion_step_bps as Balance));
+            if self.config.max_fee_multiplier_bps > 0 {
+                multiplier_bps = multiplier_bps.min(self.config.max_fee_multiplier_bps as Balance);
+            }
+            base.saturating_mul(multiplier_bps) / BASIS_POINTS
+        }
+
+        /// Get the fee an account currently pays for an operation
+        ///
+        /// Submissions include the congestion multiplier, and renewals are free until the holder has used up `free_renewals`.
+        #[ink(message)]
+        pub fn quote_fee(&self, kind: FeeKind, account: AccountId) -> Balance {
+            match kind {
+                FeeKind::Submission => self.current_submission_fee(),
+                FeeKind::Renewal
+                    if self.renewal_counts.get(&account).copied().unwrap_or(0) < self.fees.free_renewals =>
+                {
+                    0
+                }
+                kind => self.fees.fee(kind),
+            }
+        }
+
+        /// Get a page of the accounts attested by a verifier, in attestation order
+        #[ink(message)]
+        pub fn get_identities_by_verifier(&self, verifier: AccountId, offset: u32, limit: u32) -> Vec<AccountId> {
+            let count = self.attestation_counts.get(&verifier).copied().unwrap_or(0);
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+            (offset..end)
+                .filter_map(|index| self.attestations_by_verifier.get(&(verifier, index)).copied())
+                .collect()
+        }
+
+        /// Get the number of submissions awaiting verification
+        #[ink(message)]
+        pub fn get_pending_count(&self) -> u32 {
+            self.pending.len()
+        }
+
+        /// Get a page of accounts whose submissions await verification
+        #[ink(message)]
+        pub fn get_pending(&self, offset: u32, limit: u32) -> Vec<AccountId> {
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.pending.len());
+            (offset..end).filter_map(|index| self.pending.get(index).copied()).collect()
+        }
+
+        /// Get a page of expedited accounts awaiting verification
+        #[ink(message)]
+        pub fn get_pending_priority(&self, offset: u32, limit: u32) -> Vec<AccountId> {
+            let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(self.priority_pending.len());
+            (offset..end).filter_map(|index| self.priority_pending.get(index).copied()).collect()
+        }
+
+        /// Get the balance of the relayer sponsorship pool
+        #[ink(message)]
+        pub fn get_sponsorship_pool(&self) -> Balance {
+            self.sponsorship_pool
+        }
+
+        /// Get the approval of an operator acting for a holder, if still active
+        #[ink(message)]
+        pub fn get_operator(&self, holder: AccountId, operator: AccountId) -> Option<OperatorApproval> {
+            self.operators
+                .get(&(holder, operator))
+                .filter(|approval| approval.expires_at > self.env().block_timestamp())
+                .copied()
+        }
+
+        /// Get every account controlling an identity: its own account, its holder keys, the linked wallets and the active operators
+        #[ink(message)]
+        pub fn controllers_of(&self, identity_id: AccountId) -> Vec<AccountId> {
+            if self.identities.get(&identity_id).is_none() {
+                return Vec::new();
+            }
+            let mut controllers = Vec::new();
+            controllers.push(identity_id);
+            controllers.extend(self.get_holder_keys(identity_id));
+            controllers.extend(self.linked_accounts.get(&identity_id).cloned().unwrap_or_default());
+            for operator in self.operators_of.get(&identity_id).cloned().unwrap_or_default() {
+                if self.get_operator(identity_id, operator).is_some() {
+                    controllers.push(operator);
+                }
+            }
+            controllers
+        }
+
+        /// Get the identity a wallet is linked to
+        #[ink(message)]
+        pub fn linked_identity(&self, account: AccountId) -> Option<AccountId> {
+            self.linked_to.get(&account).copied()
+        }
+
+        /// Get the keys that control an identity besides its own account
+        #[ink(message)]
+        pub fn get_holder_keys(&self, identity: AccountId) -> Vec<AccountId> {
+            self.holder_keys.get(&identity).cloned().unwrap_or_default()
+        }
+
+        /// Get the identity a holder key controls
+        #[ink(message)]
+        pub fn identity_of_key(&self, key: AccountId) -> Option<AccountId> {
+            self.key_identity.get(&key).copied()
+        }
+
+        /// Check if a key currently controls an identity (its own account does while the identity exists, unless revoked)
+        #[ink(message)]
+        pub fn is_key_active(&self, account: AccountId, key: AccountId) -> bool {
+            if key == account {
+                return self.identities.get(&account).is_some() && !self.primary_key_revoked(account);
+            }
+            self.key_identity.get(&key) == Some(&account)
+        }
+
+        /// Check if a key controlled an identity at time `at`, e.g. when a past signature was made
+        #[ink(message)]
+        pub fn was_key_active(&self, account: AccountId, key: AccountId, at: Timestamp) -> bool {
+            let active_at = |record: &KeyRecord| record.added_at <= at && record.removed_at.map_or(true, |removed_at| at < removed_at);
+            if key == account {
+                return self.identities.get(&account).is_some() && self.key_records.get(&(account, key)).map_or(true, active_at);
+            }
+            self.key_records.get(&(account, key)).map_or(false, active_at)
+        }
+
+        /// Get the lifetime record of a key on an identity
+        #[ink(message)]
+        pub fn get_key_record(&self, account: AccountId, key: AccountId) -> Option<KeyRecord> {
+            self.key_records.get(&(account, key)).copied()
+        }
+
+        /// Get the latest `MAX_REVOKED_KEYS` keys revoked for an identity, oldest first
+        #[ink(message)]
+        pub fn get_revoked_keys(&self, account: AccountId) -> Vec<AccountId> {
+            self.revoked_keys.get(&account).cloned().unwrap_or_default()
+        }
+
+        /// Get the soulbound badge id held by an account
+        #[ink(message)]
+        pub fn badge_of(&self, account: AccountId) -> Option<u64> {
+            self.badges.get(&account).copied()
+        }
+
+        /// PSP34-style: get the owner of a badge
+        #[ink(message)]
+        pub fn owner_of(&self, id: u64) -> Option<AccountId> {
+            self.badge_owners.get(&id).copied()
+        }
+
+        /// PSP34-style: get the number of badges held by an account (0 or 1)
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> u32 {
+            self.badges.contains_key(&owner) as u32
+        }
+
+        /// PSP34-style: get the number of live badges
+        #[ink(message)]
+        pub fn total_supply(&self) -> u64 {
+            self.badge_owners.len() as u64
+        }
+
+        /// PSP34-style transfer, always rejected because badges are soulbound
+        #[ink(message)]
+        pub fn transfer(&mut self, _to: AccountId, _id: u64) -> Result<(), &'static str> {
+            Err("Badges are non-transferable")
+        }
+
+        /// Get the contracts notified of verification status changes
+        #[ink(message)]
+        pub fn get_hooks(&self) -> Vec<AccountId> {
+            self.hooks.iter().copied().collect()
+        }
+
+        /// Get the sibling-parachain contracts receiving broadcast attestations
+        #[ink(message)]
+        pub fn get_xcm_targets(&self) -> Vec<XcmTarget> {
+            self.xcm_targets.iter().copied().collect()
+        }
+
+        /// Check if an account is a registered oracle
+        #[ink(message)]
+        pub fn is_oracle(&self, account: AccountId) -> bool {
+            self.oracles.contains(&account)
+        }
+
+        /// Get the oracle-reported validity of a document
+        #[ink(message)]
+        pub fn get_document_status(&self, document_hash: [u8; 32]) -> Option<DocumentStatus> {
+            self.document_statuses.get(&document_hash).copied()
+        }
+
+        /// Find the account that registered a document hash (only the owner and verifiers)
+        #[ink(message)]
+        pub fn find_by_document_hash(&self, document_hash: [u8; 32]) -> Option<AccountId> {
+            let caller = self.env().caller();
+            if caller != self.owner && !self.verifiers.contains(&caller) {
+                return None;
+            }
+            self.document_index.get(&document_hash).copied()
+        }
+
+        /// Get the domain separator binding signed payloads to this chain and deployment
+        #[ink(message)]
+        pub fn domain_separator(&self) -> [u8; 32] {
+            let type_hash = self.env().hash_bytes::<ink_env::hash::Blake2x256>(DOMAIN_TYPE);
+            self.env().hash_encoded::<ink_env::hash::Blake2x256, _>(&(
+                type_hash,
+                self.config.chain_id,
+                self.env().account_id(),
+                SIGNING_VERSION,
+            ))
+        }
+
+        /// Get the nonce the next signed payload from an account must carry
+        #[ink(message)]
+        pub fn get_nonce(&self, account: AccountId) -> u64 {
+            self.nonces.get(&account).copied().unwrap_or(0)
+        }
+
+        /// Get the guardian key registered for an identity
+        #[ink(message)]
+        pub fn get_guardian(&self, account: AccountId) -> Option<[u8; 33]> {
+            self.guardians.get(&account).copied()
+        }
+
+        /// Get the compressed ECDSA public key registered by a verifier
+        #[ink(message)]
+        pub fn get_verifier_key(&self, verifier: AccountId) -> Option<[u8; 33]> {
+            self.verifier_keys.get(&verifier).copied()
+        }
+
+        /// Export the attestation of a verified account in a format EVM bridge contracts can verify
+        #[ink(message)]
+        pub fn export_attestation_evm(&self, account: AccountId) -> Result<EvmAttestation, &'static str> {
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+            let verifier = identity.verifier.ok_or("Identity not verified")?;
+            let key = self.verifier_keys.get(&verifier).ok_or("Verifier key not registered")?;
+            let verifier_address = self.env().ecdsa_to_eth_address(key).map_err(|_| "Invalid ECDSA key")?;
+
+            let mut address_word = [0u8; 32];
+            address_word[12..].copy_from_slice(&verifier_address);
+
+            let mut payload = Vec::with_capacity(5 * 32);
+            payload.extend_from_slice(account.as_ref());
+            payload.extend_from_slice(&abi_uint(self.effective_status(identity) == Status::Verified));
+            payload.extend_from_slice(&identity.proof_hash());
+            payload.extend_from_slice(&address_word);
+            payload.extend_from_slice(&abi_uint(self.env().block_number()));
+
+            let digest = self.env().hash_bytes::<ink_env::hash::Keccak256>(&payload);
+            Ok(EvmAttestation { payload, digest })
+        }
+
+        /// Get the key signing exportable attestations
+        #[ink(message)]
+        pub fn get_attestation_key(&self) -> Option<[u8; 33]> {
+            self.attestation_key
+        }
+
+        /// Get the latest signed attestation of an account, if it has not expired
+        #[ink(message)]
+        pub fn get_signed_attestation(&self, account: AccountId) -> Option<SignedAttestation> {
+            self.signed_attestations
+                .get(&account)
+                .filter(|attestation| attestation.statement.expires_at > self.env().block_timestamp())
+                .cloned()
+        }
+
+        /// Check whether the runtime identity pallet holds a positive judgement for an account
+        #[ink(message)]
+        pub fn has_runtime_judgement(&self, account: AccountId) -> Result<bool, &'static str> {
+            self.env()
+                .extension()
+                .has_judgement(account)
+                .map_err(|_| "Identity pallet query failed")
+        }
+
+        /// Mirror a verification result into the runtime identity pallet when enabled
+        fn sync_to_runtime(&self, account: AccountId, verified: bool) {
+            if !self.config.mirror_to_runtime {
+                return;
+            }
+            if self.env().extension().set_judgement(account, verified).is_err() {
+                self.env().emit_event(RuntimeSyncFailed {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                });
+            }
+        }
+
+        /// Ask the runtime scheduler to expire a verification at its deadline when enabled
+        fn schedule_expiry(&self, account: AccountId, at: Timestamp) {
+            if !self.config.schedule_expiry {
+                return;
+            }
+            if self.env().extension().schedule_expiry(account, at).is_err() {
+                self.env().emit_event(RuntimeSyncFailed {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                });
+            }
+        }
+
+        /// Status of an identity as every read path reports it, with lapsed verifications shown as expired
+        /// and every verification shown as suspended during an emergency freeze
+        fn effective_status(&self, identity: &Identity) -> Status {
+            match identity.status {
+                Status::Verified if self.is_expired(identity) => Status::Expired,
+                Status::Verified if self.frozen => Status::Suspended,
+                status => status,
+            }
+        }
+
+        /// Effective status recorded in a status word, mirroring `effective_status`
+        fn word_status(&self, word: &StatusWord) -> Status {
+            match word.status {
+                Status::Verified if word.expires_at != 0 && word.expires_at <= self.env().block_timestamp() => Status::Expired,
+                Status::Verified if self.frozen => Status::Suspended,
+                status => status,
+            }
+        }
+
+        /// Check if a verification has passed its deadline or the backing document has expired
+        fn is_expired(&self, identity: &Identity) -> bool {
+            identity
+                .lapses_at()
+                .map_or(false, |lapses_at| lapses_at <= self.env().block_timestamp())
+        }
+
+        /// Mark a lapsed verification as expired and propagate the change
+        fn expire(&mut self, account: AccountId) {
+            let now = self.env().block_timestamp();
+            let mut reason = "Verification period elapsed";
+            if let Some(identity) = self.identities.get_mut(&account) {
+                identity.status = Status::Expired;
+                if identity.document_expires_at.map_or(false, |at| at <= now) {
+                    reason = "Document expired";
+                }
+            }
+            let caller = self.env().caller();
+            self.record_history(account, Status::Expired, caller, reason);
+            self.burn_badge(account);
+            self.notify_hooks(account, Status::Expired);
+            self.sync_to_runtime(account, false);
+
+            if self.notifies(account, NOTIFY_EXPIRY) {
+                self.env().emit_event(IdentityExpired {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                });
+            }
+        }
+
+        /// Recover the compressed ECDSA public key that signed a message hash
+        fn recover_public_key(&self, message_hash: &[u8; 32], signature: &[u8; 65]) -> Result<[u8; 33], &'static str> {
+            let mut public_key = [0u8; 33];
+            ink_env::ecdsa_recover(signature, message_hash, &mut public_key).map_err(|_| "Invalid signature")?;
+            Ok(public_key)
+        }
+
+        /// Compute the digest signed for a typed payload
+        ///
+        /// `blake2x256(0x1901 ++ domain_separator ++ blake2x256(blake2x256(type) ++ SCALE(payload)))`
+        fn typed_digest<T: scale::Encode>(&self, type_descriptor: &[u8], payload: &T) -> [u8; 32] {
+            let type_hash = self.env().hash_bytes::<ink_env::hash::Blake2x256>(type_descriptor);
+            self.digest_with(&self.domain_separator(), &type_hash, payload)
+        }
+
+        /// Compute a typed-data digest from a precomputed domain separator and type hash
+        fn digest_with<T: scale::Encode>(&self, domain_separator: &[u8; 32], type_hash: &[u8; 32], payload: &T) -> [u8; 32] {
+            let struct_hash = self.env().hash_encoded::<ink_env::hash::Blake2x256, _>(&(type_hash, payload));
+            self.env()
+                .hash_encoded::<ink_env::hash::Blake2x256, _>(&([0x19u8, 0x01u8], domain_separator, struct_hash))
+        }
+
+        /// Precompute the hashes shared by every holder-signed payload
+        fn signing_context(&self) -> SigningContext {
+            SigningContext {
+                domain_separator: self.domain_separator(),
+                submission_type_hash: self.env().hash_bytes::<ink_env::hash::Blake2x256>(SUBMISSION_TYPE),
+                update_type_hash: self.env().hash_bytes::<ink_env::hash::Blake2x256>(UPDATE_TYPE),
+            }
+        }
+
+        /// Store a holder-signed submission relayed by `caller`, consuming the signer's nonce
+        fn apply_signed_submission(
+            &mut self,
+            caller: AccountId,
+            signer: AccountId,
+            submission: SubmissionPayload,
+            fee_paid: Balance,
+            deposit: Balance,
+            relayer: Option<AccountId>,
+        ) -> Result<(), &'static str> {
+            // The nonce is only consumed once the submission is stored, so a failed batch item can be retried
+            self.ensure_nonce(signer, submission.nonce)?;
+            let nonce = submission.nonce;
+
+            self.store_submission(
+                signer,
+                submission.name,
+                submission.age,
+                submission.document_id,
+                submission.proof_hash,
+                submission.jurisdiction,
+                submission.document_kind,
+                submission.document_expires_at,
+                submission.terms_hash,
+                submission.credential_type,
+                fee_paid,
+                deposit,
+                relayer,
+            )?;
+            self.use_nonce(signer, nonce)?;
+            self.env().emit_event(RelayedSubmission {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: signer,
+                relayer: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Decode a `SubmissionPayload` and recover the holder account that signed it
+        fn recover_submission(
+            &self,
+            context: &SigningContext,
+            payload: &[u8],
+            signature: &[u8; 65],
+        ) -> Result<(AccountId, SubmissionPayload), &'static str> {
+            let submission = <SubmissionPayload as scale::Decode>::decode(&mut &payload[..])
+                .map_err(|_| "Invalid submission payload")?;
+            self.ensure_before_deadline(submission.deadline)?;
+            let digest = self.digest_with(&context.domain_separator, &context.submission_type_hash, &submission);
+            let signer = self.account_from_public_key(&self.recover_public_key(&digest, signature)?);
+            Ok((signer, submission))
+        }
+
+        /// Apply a holder's permit on the fee token and pull `amount` from their balance into the treasury
+        fn pull_token_fee(&mut self, token: AccountId, holder: AccountId, amount: Balance, permit: &Permit) -> Result<(), &'static str> {
+            let contract = self.env().account_id();
+            self.set_entered(true);
+            let permitted = build_call::<Environment>()
+                .call_type(Call::new().callee(token))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(PSP22_PERMIT_SELECTOR))
+                        .push_arg(holder)
+                        .push_arg(contract)
+                        .push_arg(permit.value)
+                        .push_arg(permit.deadline)
+                        .push_arg(permit.signature),
+                )
+                .returns::<Result<(), Psp22Error>>()
+                .fire();
+            let pulled = match permitted {
+                Ok(Ok(())) => Some(
+                    build_call::<Environment>()
+                        .call_type(Call::new().callee(token))
+                        .exec_input(
+                            ExecutionInput::new(Selector::new(PSP22_TRANSFER_FROM_SELECTOR))
+                                .push_arg(holder)
+                                .push_arg(contract)
+                                .push_arg(amount)
+                                .push_arg(Vec::<u8>::new()),
+                        )
+                        .returns::<Result<(), Psp22Error>>()
+                        .fire(),
+                ),
+                _ => None,
+            };
+            self.set_entered(false);
+            permitted.map_err(|_| "Token call failed")?.map_err(|_| "Permit rejected")?;
+            if let Some(pulled) = pulled {
+                pulled.map_err(|_| "Token call failed")?.map_err(|_| "Token transfer failed")?;
+            }
+
+            self.treasury.token_collected += amount;
+            self.env().emit_event(TokenFeePaid {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account: holder,
+                token,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Decode an `UpdatePayload` and recover the holder account that signed it
+        fn recover_update(
+            &self,
+            context: &SigningContext,
+            payload: &[u8],
+            signature: &[u8; 65],
+        ) -> Result<(AccountId, UpdatePayload), &'static str> {
+            let update = <UpdatePayload as scale::Decode>::decode(&mut &payload[..]).map_err(|_| "Invalid update payload")?;
+            self.ensure_before_deadline(update.deadline)?;
+            let digest = self.digest_with(&context.domain_separator, &context.update_type_hash, &update);
+            let signer = self.account_from_public_key(&self.recover_public_key(&digest, signature)?);
+            Ok((signer, update))
+        }
+
+        /// Apply a holder-signed update to the signer's pending identity, consuming the signer's nonce
+        fn apply_signed_update(&mut self, signer: AccountId, update: UpdatePayload) -> Result<(), &'static str> {
+            self.ensure_nonce(signer, update.nonce)?;
+
+            self.apply_update(signer, signer, update.name, update.age, update.document_id, update.proof_hash)?;
+            self.use_nonce(signer, update.nonce)
+        }
+
+        /// Replace the details of a pending identity and re-index its document hash
+        fn apply_update(
+            &mut self,
+            account: AccountId,
+            updated_by: AccountId,
+            name: Name,
+            age: u32,
+            document_id: DocumentId,
+            proof_hash: [u8; 32],
+        ) -> Result<(), &'static str> {
+            self.ensure_enabled(MESSAGE_UPDATE)?;
+            if self.locked_accounts.contains(&account) {
+                return Err("Account locked after too many failed attempts");
+            }
+            let credential_type = self.identities.get(&account).map(|identity| identity.credential_type).ok_or("Identity not found")?;
+            self.validate_attributes(credential_type, &name, age, &document_id, &proof_hash)?;
+            let document_hash = self.env().hash_bytes::<ink_env::hash::Blake2x256>(trim_fixed_bytes(&document_id));
+            let now = self.env().block_timestamp();
+
+            // Ensure the identity exists and is awaiting verification or corrections, or is verified
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+            let resubmitted = identity.status == Status::ChangesRequested;
+            let reverify = identity.status == Status::Verified && identity.expires_at.map_or(true, |at| at > now);
+            if !identity.status.is_pending() && !resubmitted && !reverify {
+                return Err("Identity is not pending verification");
+            }
+            if reverify
+                && identity.name == name
+                && identity.age == age
+                && identity.document_id == document_id
+                && identity.proof_hash() == proof_hash
+            {
+                return Err("No changes to apply");
+            }
+
+            // Corrected submissions go back into the verification queue, and changed verified details must be re-verified
+            if resubmitted {
+                identity.status = Status::Pending;
+                identity.reject_reason = None;
+            }
+            if reverify {
+                identity.status = Status::PendingReverification;
+            }
+            let previous_hash = identity.document_hash;
+            identity.name = name;
+            identity.age = age;
+            identity.document_id = document_id;
+            identity.document_hash = document_hash;
+            identity.set_proof_hash(proof_hash, now);
+
+            // Re-index the document hash
+            if self.document_index.get(&previous_hash) == Some(&account) {
+                self.document_index.take(&previous_hash);
+            }
+            self.document_index.insert(document_hash, account);
+            self.clear_sign_offs(account);
+            if resubmitted || reverify {
+                let expedited = self.identities.get(&account).map_or(false, |identity| identity.priority_fee > 0);
+                self.enqueue_pending(account, expedited);
+            }
+            if resubmitted {
+                self.record_history(account, Status::Pending, updated_by, "Changes submitted");
+            }
+            if reverify {
+                self.record_history(account, Status::PendingReverification, updated_by, "Verified details changed");
+                self.burn_badge(account);
+                self.notify_hooks(account, Status::PendingReverification);
+                self.sync_to_runtime(account, false);
+                if self.notifies(account, NOTIFY_REVERIFICATION) {
+                    self.env().emit_event(ReverificationRequired {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        account,
+                        updated_by,
+                    });
+                }
+            }
+
+            self.env().emit_event(IdentityUpdated {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                updated_by,
+                proof_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Escrow the transferred bond and record a new dispute against `account`
+        fn open_dispute(
+            &mut self,
+            account: AccountId,
+            disputant: AccountId,
+            kind: DisputeKind,
+            verifier: Option<AccountId>,
+        ) -> Result<(), &'static str> {
+            if self.disputes.get(&account).is_some() {
+                return Err("Dispute already open");
+            }
+            let bond = self.env().transferred_value();
+            if bond < self.config.dispute_bond {
+                return Err("Insufficient dispute bond");
+            }
+
+            self.disputes.insert(account, Dispute {
+                kind,
+                disputant,
+                bond,
+                opened_at: self.env().block_timestamp(),
+                verifier,
+            });
+            self.env().emit_event(DisputeOpened {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                disputant,
+                kind,
+                bond,
+            });
+            Ok(())
+        }
+
+        /// Discard the sign-offs collected on a minor's application
+        fn clear_sign_offs(&mut self, account: AccountId) {
+            self.sign_offs.take(&account);
+            self.cosigned.take(&account);
+        }
+
+        /// Record the verifier a submission is routed to and emit the assignment event
+        fn set_assigned_verifier(&mut self, account: AccountId, assigned_by: AccountId, verifier: Option<AccountId>) {
+            let now = self.env().block_timestamp();
+            let deadline = match (verifier, self.config.assignment_timeout) {
+                (Some(_), timeout) if timeout > 0 => Some(now.saturating_add(timeout)),
+                _ => None,
+            };
+            let mut previous = None;
+            if let Some(identity) = self.identities.get_mut(&account) {
+                previous = identity.assigned_verifier;
+                identity.assigned_verifier = verifier;
+                identity.assignment_deadline = deadline;
+                identity.assigned_at = verifier.map(|_| now);
+            }
+
+            // Move the open request between the verifiers' workloads
+            if let Some(previous) = previous {
+                self.adjust_workload(previous, false);
+            }
+            if let Some(verifier) = verifier {
+                self.adjust_workload(verifier, true);
+            }
+            self.env().emit_event(VerifierAssigned {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier,
+                assigned_by,
+                deadline,
+            });
+            if let Some(verifier) = verifier {
+                self.env().emit_event(WorkClaimed {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    verifier,
+                    deadline,
+                });
+            }
+        }
+
+        /// Check if a targeted request has passed its response deadline
+        fn assignment_lapsed(&self, identity: &Identity) -> bool {
+            identity
+                .assignment_deadline
+                .map_or(false, |deadline| deadline <= self.env().block_timestamp())
+        }
+
+        /// Count a rejection or change request against an account, locking it once the limit is reached
+        fn record_failed_attempt(&mut self, account: AccountId) {
+            let mut failed_attempts = 0;
+            if let Some(identity) = self.identities.get_mut(&account) {
+                identity.failed_attempts = identity.failed_attempts.saturating_add(1);
+                failed_attempts = identity.failed_attempts;
+            }
+            let limit = self.config.max_failed_attempts;
+            if limit > 0 && failed_attempts >= limit && !self.locked_accounts.contains(&account) {
+                self.locked_accounts.insert(account);
+                self.env().emit_event(AccountLocked {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    failed_attempts,
+                });
+            }
+        }
+
+        /// Set the paused flag and emit the matching event
+        fn set_paused(&mut self, paused: bool) {
+            if self.paused == paused {
+                return;
+            }
+            self.paused = paused;
+            let by = self.env().caller();
+            if paused {
+                self.env().emit_event(Paused {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    by,
+                });
+            } else {
+                self.env().emit_event(Unpaused {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    by,
+                });
+            }
+        }
+
+        /// Ensure a signed payload's deadline has not passed
+        fn ensure_before_deadline(&self, deadline: Timestamp) -> Result<(), &'static str> {
+            if self.env().block_timestamp() > deadline {
+                return Err("Signature deadline passed");
+            }
+            Ok(())
+        }
+
+        /// Consume the current nonce of a signer, rejecting replayed or out-of-order payloads
+        fn use_nonce(&mut self, signer: AccountId, nonce: u64) -> Result<(), &'static str> {
+            self.ensure_nonce(signer, nonce)?;
+            self.nonces.insert(signer, nonce + 1);
+            Ok(())
+        }
+
+        /// Ensure `nonce` is the signer's next nonce, without consuming it
+        fn ensure_nonce(&self, signer: AccountId, nonce: u64) -> Result<(), &'static str> {
+            if nonce != self.get_nonce(signer) {
+                return Err("Invalid nonce");
+            }
+            Ok(())
+        }
+
+        /// Derive the account controlled by a compressed ECDSA public key
+        fn account_from_public_key(&self, public_key: &[u8; 33]) -> AccountId {
+            AccountId::from(self.env().hash_bytes::<ink_env::hash::Blake2x256>(public_key))
+        }
+
+        /// Notify every registered hook of a status change, isolating failures of individual hooks
+        fn notify_hooks(&self, account: AccountId, status: Status) {
+            self.set_entered(true);
+            for hook in self.hooks.iter() {
+                let result = build_call::<Environment>()
+                    .call_type(Call::new().callee(*hook).gas_limit(self.config.hook_gas_limit))
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ON_VERIFICATION_CHANGED_SELECTOR))
+                            .push_arg(account)
+                            .push_arg(status),
+                    )
+                    .returns::<()>()
+                    .fire();
+                if result.is_err() {
+                    self.env().emit_event(HookNotificationFailed {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        hook: *hook,
+                        account,
+                    });
+                }
+            }
+            self.set_entered(false);
+        }
+
+        /// Fail if an outbound call made by this contract is still in progress
+        ///
+        /// Cross-contract calls are made without `ALLOW_REENTRY`, so the runtime already refuses re-entry;
+        /// the guard keeps value-moving messages safe should that ever change.
+        fn ensure_not_entered(&self) -> Result<(), &'static str> {
+            let entered = ink_env::get_contract_storage::<bool>(&Key::from(REENTRANCY_GUARD_KEY))
+                .ok()
+                .flatten()
+                .unwrap_or(false);
+            if entered {
+                return Err("Reentrant call");
+            }
+            Ok(())
+        }
+
+        /// Check submitted attributes against the credential type's required attributes and schema, failing on the first invalid field
+        fn validate_attributes(
+            &self,
+            credential_type: u32,
+            name: &Name,
+            age: u32,
+            document_id: &DocumentId,
+            proof_hash: &[u8; 32],
+        ) -> Result<(), &'static str> {
+            let name = trim_fixed_bytes(name);
+            let document_id = trim_fixed_bytes(document_id);
+            if core::str::from_utf8(name).is_err() {
+                return Err("Name is not valid UTF-8");
+            }
+            if core::str::from_utf8(document_id).is_err() {
+                return Err("Document id is not valid UTF-8");
+            }
+            let credential = self.credential_types.get(&credential_type).ok_or("Credential type not found")?;
+            let schema = credential.schema;
+            if credential.required_attributes & DATA_CATEGORY_NAME != 0 && name.is_empty() {
+                return Err("Name is required");
+            }
+            if schema.max_name_length > 0 && name.len() > schema.max_name_length as usize {
+                return Err("Name too long");
+            }
+            if credential.required_attributes & DATA_CATEGORY_AGE != 0 && age == 0 {
+                return Err("Age is required");
+            }
+            if age < schema.min_age {
+                return Err("Age below the credential minimum");
+            }
+            if schema.max_age > 0 && age > schema.max_age {
+                return Err("Age above the credential maximum");
+            }
+            if credential.required_attributes & DATA_CATEGORY_DOCUMENT != 0 && document_id.is_empty() {
+                return Err("Document id is required");
+            }
+            if schema.max_document_length > 0 && document_id.len() > schema.max_document_length as usize {
+                return Err("Document id too long");
+            }
+            if schema.requires_commitment && *proof_hash == [0; 32] {
+                return Err("Proof hash commitment is required");
+            }
+            Ok(())
+        }
+
+        /// Lifetime of a verification of `credential_type`, falling back to the configured `verification_validity`
+        /// and capped by the validity of the backing document kind
+        fn verification_validity(&self, credential_type: u32, document_kind: DocumentKind) -> Timestamp {
+            let validity = match self.credential_types.get(&credential_type).map(|credential| credential.validity) {
+                Some(validity) if validity > 0 => validity,
+                _ => self.config.verification_validity,
+            };
+            match self.document_rules_of(document_kind).validity {
+                0 => validity,
+                cap if validity == 0 || cap < validity => cap,
+                _ => validity,
+            }
+        }
+
+        /// Drop every field commitment, the biometric record and the linked wallets of an account
+        fn clear_sensitive_data(&mut self, account: AccountId) {
+            self.biometrics.take(&account);
+            for field in self.committed_fields.take(&account).unwrap_or_default() {
+                self.field_commitments.take(&(account, field));
+            }
+            for linked in self.linked_accounts.get(&account).cloned().unwrap_or_default() {
+                self.unlink(account, linked);
+            }
+        }
+
+        /// Check if an identity's own account was revoked as one of its keys
+        fn primary_key_revoked(&self, identity: AccountId) -> bool {
+            self.key_records.get(&(identity, identity)).map_or(false, |record| record.revoked)
+        }
+
+        /// Ensure an identity keeps an active key once `key` stops controlling it
+        fn ensure_other_key_active(&self, identity: AccountId, key: AccountId) -> Result<(), &'static str> {
+            let keys = self.holder_keys.get(&identity).map_or(0, |keys| keys.len());
+            let others = if key == identity { keys } else { keys - 1 + usize::from(!self.primary_key_revoked(identity)) };
+            if others == 0 {
+                return Err("Identity needs another active key");
+            }
+            Ok(())
+        }
+
+        /// Remove a holder key from an identity and emit `HolderKeyRemoved`
+        fn remove_key(&mut self, identity: AccountId, key: AccountId, removed_by: AccountId) {
+            let now = self.env().block_timestamp();
+            if let Some(record) = self.key_records.get_mut(&(identity, key)) {
+                record.removed_at = Some(now);
+            }
+            self.key_identity.take(&key);
+            if let Some(keys) = self.holder_keys.get_mut(&identity) {
+                keys.retain(|listed| *listed != key);
+            }
+            self.env().emit_event(HolderKeyRemoved {
+                schema_version: EVENT_SCHEMA_VERSION,
+                identity,
+                key,
+                removed_by,
+            });
+        }
+
+        /// Remove a wallet from an identity's linked accounts and emit `AccountUnlinked`
+        fn unlink(&mut self, identity: AccountId, account: AccountId) {
+            self.linked_to.take(&account);
+            if let Some(linked) = self.linked_accounts.get_mut(&identity) {
+                linked.retain(|listed| *listed != account);
+            }
+            self.env().emit_event(AccountUnlinked {
+                schema_version: EVENT_SCHEMA_VERSION,
+                identity,
+                account,
+            });
+        }
+
+        /// Index of the first ledger entry recorded in or after an epoch, found by binary search
+        fn ledger_lower_bound(&self, epoch: u32) -> u32 {
+            let (mut low, mut high) = (0, self.ledger_len);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if self.ledger.get(&mid).map_or(false, |entry| entry.epoch < epoch) {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            low
+        }
+
+        /// Check if an account still receives a lifecycle notification
+        fn notifies(&self, account: AccountId, flag: u32) -> bool {
+            self.get_notification_flags(account) & flag != 0
+        }
+
+        /// Rules of a document kind, or the defaults if the owner has not set any
+        fn document_rules_of(&self, document_kind: DocumentKind) -> DocumentRules {
+            self.document_rules.get(&document_kind).copied().unwrap_or_default()
+        }
+
+        /// Check if a verifier holds one of the specializations the document kind allows
+        fn is_specialized(&self, verifier: AccountId, document_kind: DocumentKind) -> bool {
+            let allowed = self.document_rules_of(document_kind).allowed_specializations;
+            allowed == 0 || self.verifier_specializations.get(&verifier).copied().unwrap_or(0) & allowed != 0
+        }
+
+        /// Whether this code is being delegate-called by the forwarder contract
+        fn is_forwarded(&self) -> bool {
+            ink_env::get_contract_storage::<[u8; 32]>(&Key::from(FORWARDER_LOGIC_KEY))
+                .ok()
+                .flatten()
+                .is_some()
+        }
+
+        /// Engage or release the reentrancy guard around an outbound call
+        fn set_entered(&self, entered: bool) {
+            ink_env::set_contract_storage(&Key::from(REENTRANCY_GUARD_KEY), &entered);
+        }
+
+        /// Approve a pending identity on behalf of `verifier`, holding it for the challenge period if one is configured
+        fn apply_verification(&mut self, account: AccountId, verifier: AccountId, proof_hash: [u8; 32]) -> Result<(), &'static str> {
+            self.ensure_enabled(MESSAGE_VERIFY)?;
+            self.ensure_routed_to(account, verifier)?;
+            let now = self.env().block_timestamp();
+            // Ensure the identity exists and is not already verified
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+            if identity.status == Status::Verified {
+                return Err("Identity already verified");
+            }
+            if !identity.status.is_pending() {
+                return Err("Identity is not pending verification");
+            }
+
+            // Ensure the proof hash matches the stored one
+            if identity.proof_hash() != proof_hash {
+                return Err("Proof hash does not match");
+            }
+            if identity.document_expires_at.map_or(false, |at| at <= now) {
+                return Err("Document has expired");
+            }
+            let (document_hash, document_kind, jurisdiction, age) =
+                (identity.document_hash, identity.document_kind, identity.jurisdiction, identity.age);
+
+            if self.denylist.contains(&DenylistEntry::Account(account))
+                || self.denylist.contains(&DenylistEntry::Document(document_hash))
+            {
+                return Err("Identity is denylisted");
+            }
+
+            // Ensure the verifier is licensed for the identity's jurisdiction
+            if !self.accreditations.contains(&(verifier, jurisdiction)) {
+                return Err("Verifier not accredited for the jurisdiction");
+            }
+            if !self.is_specialized(verifier, document_kind) {
+                return Err("Verifier lacks the specialization for the document kind");
+            }
+
+            // Ensure the oracle has not flagged the document (or has approved it, if required)
+            match self.document_statuses.get(&document_hash) {
+                Some(DocumentStatus::Invalid) => return Err("Document reported invalid"),
+                None if self.config.require_valid_document => return Err("Document not validated"),
+                _ => {}
+            }
+
+            // Minors need a second sign-off from their designated co-signer or another verifier
+            let threshold = self
+                .ages_of_majority
+                .get(&jurisdiction)
+                .copied()
+                .unwrap_or(self.config.minor_age_threshold);
+            if threshold > 0 && age < threshold && !self.cosigned.contains(&account) {
+                match self.sign_offs.get(&account).copied() {
+                    Some(first) if first == verifier => return Err("Second sign-off must come from another party"),
+                    Some(_) => {}
+                    None => {
+                        self.sign_offs.insert(account, verifier);
+                        self.env().emit_event(SignOffRecorded {
+                            schema_version: EVENT_SCHEMA_VERSION,
+                            account,
+                            signer: verifier,
+                        });
+                        return Ok(());
+                    }
+                }
+            }
+
+            // Hold the approval open to contest for the challenge period before it takes effect
+            let challenged = self.config.challenge_period > 0;
+            if !challenged {
+                self.ensure_can_pay(self.verification_payout(account))?;
+            }
+            if let Some(identity) = self.identities.get_mut(&account) {
+                identity.verifier = Some(verifier);
+                if challenged {
+                    identity.status = Status::Approved;
+                }
+            }
+            self.sign_offs.take(&account);
+            self.cosigned.take(&account);
+            if challenged {
+                self.remove_pending(account);
+                let finalizes_at = self.env().block_number() + self.config.challenge_period;
+                self.approvals.insert(account, finalizes_at);
+                self.record_history(account, Status::Approved, verifier, "Approved");
+                self.env().emit_event(VerificationApproved {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    verifier,
+                    finalizes_at,
+                });
+                return Ok(());
+            }
+
+            self.complete_verification(account, verifier)
+        }
+
+        /// Mark an approved identity as verified by `verifier` and settle its fees, bounty and deposit
+        fn complete_verification(&mut self, account: AccountId, verifier: AccountId) -> Result<(), &'static str> {
+            // Ensure the payouts can be made before any state changes, so a batch item never half-settles
+            self.ensure_can_pay(self.verification_payout(account))?;
+            let now = self.env().block_timestamp();
+            let (credential_type, document_kind) = self
+                .identities
+                .get(&account)
+                .map(|identity| (identity.credential_type, identity.document_kind))
+                .ok_or("Identity not found")?;
+            let validity = self.verification_validity(credential_type, document_kind);
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+
+            // Mark the identity as verified
+            identity.status = Status::Verified;
+            identity.verifier = Some(verifier);
+            identity.verified_at = now;
+            if let Some(entry) = identity.proofs.first_mut() {
+                entry.attested_by = Some(verifier);
+            }
+            identity.expires_at = match validity {
+                0 => None,
+                validity => Some(now.saturating_add(validity)),
+            };
+            let lapses_at = identity.lapses_at();
+            let fee_paid = identity.fee_paid;
+            let deposit = identity.deposit;
+            let priority_fee = identity.priority_fee;
+            identity.fee_paid = 0;
+            identity.deposit = 0;
+            identity.priority_fee = 0;
+            let relayer = identity.relayer;
+            let credential_type = identity.credential_type;
+            let document_hash = identity.document_hash;
+            self.remove_pending(account);
+            let document_topic = self.document_topic(document_hash);
+            let mut stats = self.credential_stats.get(&credential_type).copied().unwrap_or_default();
+            stats.verified += 1;
+            self.credential_stats.insert(credential_type, stats);
+
+            // Record the attestation in the verifier's index
+            let attestation_count = self.attestation_counts.get(&verifier).copied().unwrap_or(0);
+            self.attestations_by_verifier.insert((verifier, attestation_count), account);
+            self.attestation_counts.insert(verifier, attestation_count + 1);
+            self.record_history(account, Status::Verified, verifier, "Verified");
+            self.record_ledger_entry(account, Status::Verified);
+            let verifications = self.verification_counts.get(&account).copied().unwrap_or(0);
+            self.verification_counts.insert(account, verifications + 1);
+            self.record_decision(account, verifier);
+            self.kyc_levels.insert(account, KycLevel::Basic);
+            self.kyc_attestations.insert((account, KycLevel::Basic), verifier);
+
+            // Emit an event for identity verification
+            self.env().emit_event(IdentityVerified {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier,
+                document_topic,
+            });
+            self.issue_certificate(account, verifier);
+            self.mint_badge(account);
+            self.notify_hooks(account, Status::Verified);
+            self.sync_to_runtime(account, true);
+            if let Some(lapses_at) = lapses_at {
+                self.schedule_expiry(account, lapses_at);
+            }
+
+            self.split_fee(account, verifier, fee_paid);
+            self.pay_priority_fee(verifier, priority_fee);
+
+            // Pay out any escrowed bounty to the verifier
+            if let Some(bounty) = self.bounties.take(&account) {
+                self.env().transfer(verifier, bounty.amount).map_err(|_| "Transfer failed")?;
+                self.env().emit_event(BountyPaid {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    verifier,
+                    amount: bounty.amount,
+                });
+            }
+
+            // Return the deposit to the holder
+            if deposit > 0 {
+                self.env().transfer(account, deposit).map_err(|_| "Transfer failed")?;
+                self.env().emit_event(DepositReturned {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    amount: deposit,
+                });
+            }
+
+            // Reimburse the relayer of a sponsored submission while the pool lasts
+            let reimbursement = self.config.relayer_reimbursement;
+            if let Some(relayer) = relayer {
+                if reimbursement > 0 && self.sponsorship_pool >= reimbursement {
+                    self.sponsorship_pool -= reimbursement;
+                    self.env().transfer(relayer, reimbursement).map_err(|_| "Transfer failed")?;
+                    self.env().emit_event(RelayerReimbursed {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        relayer,
+                        account,
+                        amount: reimbursement,
+                    });
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Total the bounty, deposit and relayer reimbursement paid out when an identity is verified
+        fn verification_payout(&self, account: AccountId) -> Balance {
+            let bounty = self.bounties.get(&account).map_or(0, |bounty| bounty.amount);
+            let (deposit, relayer) = self
+                .identities
+                .get(&account)
+                .map_or((0, None), |identity| (identity.deposit, identity.relayer));
+            let reimbursement = self.config.relayer_reimbursement;
+            let reimbursed = match relayer {
+                Some(_) if reimbursement > 0 && self.sponsorship_pool >= reimbursement => reimbursement,
+                _ => 0,
+            };
+            bounty.saturating_add(deposit).saturating_add(reimbursed)
+        }
+
+        /// Build the detailed status of an identity, withholding the verifier unless `privileged`
+        fn status_of(&self, identity: &Identity, privileged: bool) -> IdentityStatus {
+            IdentityStatus {
+                status: self.effective_status(identity),
+                verifier: if privileged { identity.verifier } else { None },
+                verified_at: identity.verified_at,
+                expires_at: identity.expires_at,
+                credential_type: identity.credential_type,
+                attempt_count: identity.attempt_count,
+                certificate_id: identity.certificate_id,
+                jurisdiction: identity.jurisdiction,
+            }
+        }
+
+        /// Check if a rejected, revoked or expired record has outlived its retention window
+        fn retention_lapsed(&self, identity: &Identity) -> bool {
+            let (retention, since) = match self.effective_status(identity) {
+                Status::Rejected | Status::Withdrawn => (self.config.rejected_retention, identity.status_changed_at),
+                Status::Revoked => (self.config.revoked_retention, identity.status_changed_at),
+                Status::Expired => (
+                    self.config.expired_retention,
+                    identity.expires_at.map_or(identity.status_changed_at, |expires_at| expires_at.max(identity.status_changed_at)),
+                ),
+                _ => return false,
+            };
+            retention > 0 && since.saturating_add(retention) <= self.env().block_timestamp()
+        }
+
+        /// Delete a record and the personal data derived from it, keeping its status history
+        fn purge(&mut self, account: AccountId, purged_by: AccountId) {
+            let identity = match self.identities.take(&account) {
+                Some(identity) => identity,
+                None => return,
+            };
+            let status = self.effective_status(&identity);
+            if self.document_index.get(&identity.document_hash) == Some(&account) {
+                self.document_index.take(&identity.document_hash);
+            }
+            self.kyc_levels.take(&account);
+            self.signed_attestations.take(&account);
+            self.erasure_requests.take(&account);
+            self.cosigners.take(&account);
+            self.guardians.take(&account);
+            for key in self.get_holder_keys(account) {
+                self.remove_key(account, key, purged_by);
+            }
+            self.clear_sensitive_data(account);
+            self.record_history(account, status, purged_by, "Purged");
+
+            if self.notifies(account, NOTIFY_PURGE) {
+                self.env().emit_event(IdentityPurged {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    status,
+                });
+            }
+        }
+
+        /// Salt a document hash with the contract address for use as an event topic
+        fn document_topic(&self, document_hash: [u8; 32]) -> [u8; 32] {
+            self.env().hash_encoded::<ink_env::hash::Blake2x256, _>(&(self.env().account_id(), document_hash))
+        }
+
+        /// Reject a pending identity on behalf of `verifier` and settle its held fee and deposit
+        fn apply_rejection(&mut self, account: AccountId, verifier: AccountId, reason: RejectReason) -> Result<(), &'static str> {
+            self.ensure_enabled(MESSAGE_REJECT)?;
+            self.ensure_routed_to(account, verifier)?;
+            // Ensure the identity exists and is awaiting verification
+            let identity = self.identities.get(&account).ok_or("Identity not found")?;
+            if !identity.status.is_pending() {
+                return Err("Identity is not pending verification");
+            }
+
+            // Ensure the refund and deposit can be paid out before any state changes, so a batch item never half-settles
+            let processing_cut = identity.fee_paid * self.config.rejection_cut_bps as Balance / BASIS_POINTS;
+            let refund = identity.fee_paid - processing_cut;
+            let payout = refund.saturating_add(identity.deposit);
+            if reason != RejectReason::SuspectedFraud {
+                self.ensure_can_pay(payout)?;
+            }
+            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
+
+            // Mark the identity as rejected and release the held fee and deposit
+            identity.status = Status::Rejected;
+            identity.verifier = Some(verifier);
+            identity.reject_reason = Some(reason);
+            let fee_paid = identity.fee_paid;
+            let deposit = identity.deposit;
+            let priority_fee = identity.priority_fee;
+            identity.fee_paid = 0;
+            identity.deposit = 0;
+            identity.priority_fee = 0;
+            self.remove_pending(account);
+            self.pay_priority_fee(verifier, priority_fee);
+            self.clear_sign_offs(account);
+            let description = match reason {
+                RejectReason::DocumentIllegible => "Rejected: document illegible",
+                RejectReason::HashMismatch => "Rejected: proof hash mismatch",
+                RejectReason::Expired => "Rejected: document expired",
+                RejectReason::SuspectedFraud => "Rejected: suspected fraud",
+                RejectReason::Other(_) => "Rejected",
+            };
+            self.record_history(account, Status::Rejected, verifier, description);
+            self.record_decision(account, verifier);
+
+            self.env().emit_event(IdentityRejected {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                verifier,
+                reason,
+            });
+            self.record_failed_attempt(account);
+
+            // Forfeit the fee and deposit to the treasury if the submission is suspected fraud
+            if reason == RejectReason::SuspectedFraud {
+                self.treasury.total_collected += fee_paid + deposit;
+                if deposit > 0 {
+                    self.env().emit_event(DepositSlashed {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        account,
+                        amount: deposit,
+                    });
+                }
+                return Ok(());
+            }
+
+            // Return the deposit to the holder
+            if deposit > 0 {
+                self.env().transfer(account, deposit).map_err(|_| "Transfer failed")?;
+                self.env().emit_event(DepositReturned {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    amount: deposit,
+                });
+            }
+
+            // Refund the fee minus the processing cut, which stays in the treasury
+            self.treasury.total_collected += processing_cut;
+            if refund > 0 {
+                self.env().transfer(account, refund).map_err(|_| "Transfer failed")?;
+                self.treasury.total_refunded = self.treasury.total_refunded.saturating_add(refund);
+                self.env().emit_event(SubmissionRefunded {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    amount: refund,
+                    processing_cut,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Record a verifier's decision time and penalize the verifier if a claimed request missed the SLA
+        fn record_decision(&mut self, account: AccountId, verifier: AccountId) {
+            let now = self.env().block_timestamp();
+            let mut claimed_at = None;
+            let mut review_time = 0;
+            let mut assigned = None;
+            if let Some(identity) = self.identities.get_mut(&account) {
+                review_time = now.saturating_sub(identity.submitted_at);
+                identity.review_time = review_time;
+                assigned = identity.assigned_verifier;
+                if assigned == Some(verifier) {
+                    claimed_at = identity.assigned_at;
+                }
+            }
+            if let Some(assigned) = assigned {
+                self.adjust_workload(assigned, false);
+            }
+
+            let mut stats = self.verifier_stats.get(&verifier).copied().unwrap_or_default();
+            stats.decisions += 1;
+            stats.total_review_time += review_time;
+            let sla = self.config.verification_sla;
+            if let Some(claimed_at) = claimed_at {
+                let elapsed = now.saturating_sub(claimed_at);
+                if sla > 0 && elapsed > sla {
+                    stats.sla_breaches += 1;
+                    let reputation = self.verifier_reputation.get(&verifier).copied().unwrap_or(0);
+                    self.verifier_reputation.insert(verifier, reputation.saturating_sub(1));
+                    self.env().emit_event(SlaBreached {
+                        schema_version: EVENT_SCHEMA_VERSION,
+                        verifier,
+                        account,
+                        elapsed,
+                    });
+                }
+            }
+            self.verifier_stats.insert(verifier, stats);
+        }
+
+        /// Remove an account from the priority lane
+        fn remove_priority(&mut self, account: AccountId) {
+            if let Some(index) = self.priority_positions.take(&account) {
+                let last = self.priority_pending.len() - 1;
+                if index != last {
+                    self.priority_pending.swap(index, last);
+                    if let Some(moved) = self.priority_pending.get(index).copied() {
+                        self.priority_positions.insert(moved, index);
+                    }
+                }
+                self.priority_pending.pop();
+            }
+        }
+
+        /// Revoke a verified identity and propagate the change
+        fn revoke(&mut self, account: AccountId, revoked_by: AccountId) {
+            let mut credential_type = None;
+            if let Some(identity) = self.identities.get_mut(&account) {
+                identity.status = Status::Revoked;
+                credential_type = Some(identity.credential_type);
+            }
+            if let Some(credential_type) = credential_type {
+                let mut stats = self.credential_stats.get(&credential_type).copied().unwrap_or_default();
+                stats.revoked += 1;
+                self.credential_stats.insert(credential_type, stats);
+            }
+            self.record_history(account, Status::Revoked, revoked_by, "Revoked");
+            self.record_ledger_entry(account, Status::Revoked);
+            self.burn_badge(account);
+            self.notify_hooks(account, Status::Revoked);
+            self.sync_to_runtime(account, false);
+
+            self.env().emit_event(IdentityRevoked {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                revoked_by,
+            });
+        }
+
+        /// Account whose identity a denylist entry covers, if any
+        fn account_of(&self, entry: DenylistEntry) -> Option<AccountId> {
+            match entry {
+                DenylistEntry::Account(account) => Some(account),
+                DenylistEntry::Document(document_hash) => self.document_index.get(&document_hash).copied(),
+            }
+        }
+
+        /// Check whether an identity's account or document is denylisted
+        fn is_listed(&self, account: AccountId) -> bool {
+            self.denylist.contains(&DenylistEntry::Account(account))
+                || self
+                    .identities
+                    .get(&account)
+                    .map_or(false, |identity| self.denylist.contains(&DenylistEntry::Document(identity.document_hash)))
+        }
+
+        /// Suspend a verified identity whose account or document was denylisted
+        fn suspend(&mut self, account: AccountId, by: AccountId) {
+            match self.identities.get_mut(&account) {
+                Some(identity) if identity.status == Status::Verified => identity.status = Status::Suspended,
+                _ => return,
+            }
+            self.record_history(account, Status::Suspended, by, "Denylisted");
+            self.burn_badge(account);
+            self.notify_hooks(account, Status::Suspended);
+            self.sync_to_runtime(account, false);
+        }
+
+        /// Reinstate a suspended identity once neither its account nor its document is denylisted
+        fn reinstate(&mut self, account: AccountId, by: AccountId) {
+            if self.is_listed(account) {
+                return;
+            }
+            match self.identities.get_mut(&account) {
+                Some(identity) if identity.status == Status::Suspended => identity.status = Status::Verified,
+                _ => return,
+            }
+            self.record_history(account, Status::Verified, by, "Delisted");
+            self.mint_badge(account);
+            self.notify_hooks(account, Status::Verified);
+            self.sync_to_runtime(account, true);
+        }
+
+        /// Mint a soulbound badge to a newly verified holder
+        fn mint_badge(&mut self, account: AccountId) {
+            let id = self.next_badge_id;
+            self.next_badge_id += 1;
+            self.badges.insert(account, id);
+            self.badge_owners.insert(id, account);
+            self.env().emit_event(Transfer {
+                schema_version: EVENT_SCHEMA_VERSION,
+                from: None,
+                to: Some(account),
+                id,
+            });
+        }
+
+        /// Count a submission against the per-account and per-block rate limits, failing if either is reached
+        fn enforce_rate_limits(&mut self, account: AccountId) -> Result<(), &'static str> {
+            let now = self.env().block_timestamp();
+            let block = self.env().block_number();
+
+            let per_block = self.config.max_submissions_per_block;
+            let (counted_block, block_count) = self.block_submissions;
+            let block_count = if counted_block == block { block_count } else { 0 };
+            if per_block > 0 && block_count >= per_block {
+                return Err("Block submission limit reached");
+            }
+
+            let per_window = self.config.max_submissions_per_window;
+            let (window_start, window_count) = match self.submission_windows.get(&account).copied() {
+                Some((start, count)) if now < start.saturating_add(self.config.rate_limit_window) => (start, count),
+                _ => (now, 0),
+            };
+            if per_window > 0 && window_count >= per_window {
+                return Err("Account submission rate limit reached");
+            }
 
-    /// Identity struct to store user information
-    #[derive(Debug, Clone, PartialEq, Eq, PackedLayout, SpreadLayout)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub struct Identity {
-        name: String,
-        age: u32,
-        document_id: String,
-        proof_hash: [u8; 32],  // 32-byte array to store the hash
-        is_verified: bool,
-        verifier: Option<AccountId>, // Optional verifier address
-    }
+            self.block_submissions = (block, block_count + 1);
+            self.submission_windows.insert(account, (window_start, window_count + 1));
+            Ok(())
+        }
 
-    #[ink(storage)]
-    #[derive(SpreadAllocate)]
-    pub struct DIDVerifier {
-        identities: ink_storage::collections::HashMap<AccountId, Identity>, // Mapping from account to Identity
-        verifiers: ink_storage::collections::HashSet<AccountId>,            // Set of approved verifiers
-        owner: AccountId,                                                  // Contract owner
-    }
+        /// Age of majority in a jurisdiction, falling back to `minor_age_threshold`
+        fn age_of_majority(&self, jurisdiction: [u8; 2]) -> u32 {
+            self.ages_of_majority
+                .get(&jurisdiction)
+                .copied()
+                .unwrap_or(self.config.minor_age_threshold)
+        }
 
-    #[ink(event)]
-    pub struct IdentitySubmitted {
-        #[ink(topic)]
-        account: AccountId,
-        name: String,
-        age: u32,
-        proof_hash: [u8; 32],
-    }
+        /// Emit a consent receipt for `subject`, stamped with the terms currently in force
+        fn emit_consent_receipt(&self, subject: AccountId, recipient: Option<AccountId>, purpose: u32, data_categories: u32) {
+            let terms_hash = self.terms.get(&self.terms_version).copied().unwrap_or([0; 32]);
+            self.env().emit_event(ConsentReceipt {
+                schema_version: EVENT_SCHEMA_VERSION,
+                subject,
+                recipient,
+                purpose,
+                data_categories,
+                terms_hash,
+                timestamp: self.env().block_timestamp(),
+            });
+        }
 
-    #[ink(event)]
-    pub struct IdentityVerified {
-        #[ink(topic)]
-        account: AccountId,
-        #[ink(topic)]
-        verifier: AccountId,
-    }
+        /// Check that `terms_hash` is the current terms version, returning that version (0 while no terms are published)
+        fn ensure_current_terms(&self, terms_hash: [u8; 32]) -> Result<u32, &'static str> {
+            if self.terms_version == 0 {
+                return Ok(0);
+            }
+            if self.terms.get(&self.terms_version) != Some(&terms_hash) {
+                return Err("Terms hash does not match the current terms");
+            }
+            Ok(self.terms_version)
+        }
 
-    impl DIDVerifier {
-        /// Constructor initializes the owner as the contract deployer
-        #[ink(constructor)]
-        pub fn new() -> Self {
-            let caller = Self::env().caller();
-            ink_lang::codegen::initialize_contract(|contract: &mut Self| {
-                contract.owner = caller;
-                contract.verifiers = ink_storage::collections::HashSet::new();
-                contract.identities = ink_storage::collections::HashMap::new();
-            })
+        /// Issue a certificate for the attestation just recorded on an identity
+        fn issue_certificate(&mut self, account: AccountId, verifier: AccountId) {
+            let id = self.next_certificate_id;
+            self.next_certificate_id += 1;
+            let issued_at = self.env().block_timestamp();
+            if let Some(identity) = self.identities.get_mut(&account) {
+                identity.certificate_id = Some(id);
+                let certificate = Certificate {
+                    account,
+                    verifier: Some(verifier),
+                    proof_hash: identity.proof_hash(),
+                    issued_at,
+                    expires_at: identity.lapses_at(),
+                };
+                self.certificates.insert(id, certificate);
+            }
+            self.env().emit_event(CertificateIssued {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                certificate_id: id,
+            });
         }
 
-        /// Submit identity for verification
-        #[ink(message)]
-        pub fn submit_identity(
+        /// Burn the soulbound badge of a holder who lost verified status
+        fn burn_badge(&mut self, account: AccountId) {
+            if let Some(id) = self.badges.take(&account) {
+                self.badge_owners.take(&id);
+                self.env().emit_event(Transfer {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    from: Some(account),
+                    to: None,
+                    id,
+                });
+            }
+        }
+
+        /// Initialize every storage field of a freshly deployed contract administered by `owner`
+        fn init(contract: &mut Self, owner: AccountId) {
+            contract.owner = owner;
+            contract.verifiers = ink_storage::collections::HashSet::new();
+            contract.identities = ink_storage::collections::HashMap::new();
+            contract.config = Config::default();
+            contract.treasurers = ink_storage::collections::HashSet::new();
+            contract.treasury = Treasury::default();
+            contract.bounties = ink_storage::collections::HashMap::new();
+            contract.subscriptions = ink_storage::collections::HashMap::new();
+            contract.earnings = ink_storage::collections::HashMap::new();
+            contract.relayers = ink_storage::collections::HashSet::new();
+            contract.sponsorship_pool = 0;
+            contract.pending = ink_storage::collections::Vec::new();
+            contract.pending_positions = ink_storage::collections::HashMap::new();
+            contract.priority_pending = ink_storage::collections::Vec::new();
+            contract.priority_positions = ink_storage::collections::HashMap::new();
+            contract.badges = ink_storage::collections::HashMap::new();
+            contract.badge_owners = ink_storage::collections::HashMap::new();
+            contract.next_badge_id = 0;
+            contract.hooks = ink_storage::collections::Vec::new();
+            contract.relying_parties = ink_storage::collections::HashSet::new();
+            contract.xcm_targets = ink_storage::collections::Vec::new();
+            contract.verifier_keys = ink_storage::collections::HashMap::new();
+            contract.oracles = ink_storage::collections::HashSet::new();
+            contract.document_index = ink_storage::collections::HashMap::new();
+            contract.document_statuses = ink_storage::collections::HashMap::new();
+            contract.attestation_key = None;
+            contract.signed_attestations = ink_storage::collections::HashMap::new();
+            contract.nonces = ink_storage::collections::HashMap::new();
+            contract.operators = ink_storage::collections::HashMap::new();
+            contract.guardians = ink_storage::collections::HashMap::new();
+            contract.attestations_by_verifier = ink_storage::collections::HashMap::new();
+            contract.attestation_counts = ink_storage::collections::HashMap::new();
+            contract.credential_stats = ink_storage::collections::HashMap::new();
+            contract.history = ink_storage::collections::HashMap::new();
+            contract.history_counts = ink_storage::collections::HashMap::new();
+            contract.accounts = ink_storage::collections::Vec::new();
+            contract.paused = false;
+            contract.frozen = false;
+            contract.locked_accounts = ink_storage::collections::HashSet::new();
+            contract.kyc_levels = ink_storage::collections::HashMap::new();
+            contract.kyc_attestations = ink_storage::collections::HashMap::new();
+            contract.verifier_tiers = ink_storage::collections::HashMap::new();
+            contract.disputes = ink_storage::collections::HashMap::new();
+            contract.verifier_reputation = ink_storage::collections::HashMap::new();
+            contract.renewals = ink_storage::collections::HashMap::new();
+            contract.verifier_stats = ink_storage::collections::HashMap::new();
+            contract.approvals = ink_storage::collections::HashMap::new();
+            contract.cosigners = ink_storage::collections::HashMap::new();
+            contract.sign_offs = ink_storage::collections::HashMap::new();
+            contract.cosigned = ink_storage::collections::HashSet::new();
+            contract.certificates = ink_storage::collections::HashMap::new();
+            contract.next_certificate_id = 0;
+            contract.audit_log = ink_storage::collections::HashMap::new();
+            contract.audit_count = 0;
+            contract.erasure_requests = ink_storage::collections::HashMap::new();
+            contract.terms = ink_storage::collections::HashMap::new();
+            contract.terms_version = 0;
+            contract.min_terms_version = 0;
+            contract.accreditations = ink_storage::collections::HashSet::new();
+            contract.denylist = ink_storage::collections::HashSet::new();
+            contract.compliance_officers = ink_storage::collections::HashSet::new();
+            contract.legal_holds = ink_storage::collections::HashMap::new();
+            contract.read_grants = ink_storage::collections::HashMap::new();
+            contract.ages_of_majority = ink_storage::collections::HashMap::new();
+            contract.submission_windows = ink_storage::collections::HashMap::new();
+            contract.block_submissions = (0, 0);
+            contract.storage_version = STORAGE_VERSION;
+            contract.pending_upgrade = None;
+            contract.migration_target = None;
+            contract.migration_source = None;
+            contract.credential_types = ink_storage::collections::HashMap::new();
+            contract.status_words = ink_storage::collections::HashMap::new();
+            contract.document_rules = ink_storage::collections::HashMap::new();
+            contract.verifier_specializations = ink_storage::collections::HashMap::new();
+            contract.field_commitments = ink_storage::collections::HashMap::new();
+            contract.committed_fields = ink_storage::collections::HashMap::new();
+            contract.biometrics = ink_storage::collections::HashMap::new();
+            contract.notification_flags = ink_storage::collections::HashMap::new();
+            contract.renewal_counts = ink_storage::collections::HashMap::new();
+            contract.ledger = ink_storage::collections::HashMap::new();
+            contract.ledger_len = 0;
+            contract.aliases = ink_storage::collections::HashMap::new();
+            contract.operators_of = ink_storage::collections::HashMap::new();
+            contract.linked_accounts = ink_storage::collections::HashMap::new();
+            contract.linked_to = ink_storage::collections::HashMap::new();
+            contract.pending_links = ink_storage::collections::HashMap::new();
+            contract.holder_keys = ink_storage::collections::HashMap::new();
+            contract.key_identity = ink_storage::collections::HashMap::new();
+            contract.key_records = ink_storage::collections::HashMap::new();
+            contract.revoked_keys = ink_storage::collections::HashMap::new();
+            contract.verification_counts = ink_storage::collections::HashMap::new();
+            contract.dispute_counts = ink_storage::collections::HashMap::new();
+            contract.verifier_timeouts = ink_storage::collections::HashMap::new();
+            contract.alias_of = ink_storage::collections::HashMap::new();
+            contract.disabled_messages = 0;
+            contract.fees = FeeSchedule::default();
+            contract.alias_period = 0;
+            contract.credential_types.insert(
+                DEFAULT_CREDENTIAL_TYPE,
+                CredentialType {
+                    name_hash: [0; 32],
+                    required_attributes: DATA_CATEGORY_ALL,
+                    schema: CredentialSchema::default(),
+                    validity: 0,
+                    active: true,
+                },
+            );
+        }
+
+        /// Store a new pending identity for `account` and emit the submission event
+        #[allow(clippy::too_many_arguments)]
+        fn store_submission(
             &mut self,
-            name: String,
+            account: AccountId,
+            name: Name,
             age: u32,
-            document_id: String,
+            document_id: DocumentId,
             proof_hash: [u8; 32],
+            jurisdiction: [u8; 2],
+            document_kind: DocumentKind,
+            document_expires_at: Option<Timestamp>,
+            terms_hash: [u8; 32],
+            credential_type: u32,
+            fee_paid: Balance,
+            deposit: Balance,
+            relayer: Option<AccountId>,
         ) -> Result<(), &'static str> {
-            let caller = self.env().caller();
-            // Ensure identity does not already exist for this account
-            if self.identities.contains_key(&caller) {
-                return Err("Identity already submitted");
+            self.ensure_enabled(MESSAGE_SUBMIT)?;
+            let terms_version = self.ensure_current_terms(terms_hash)?;
+            if self.key_identity.get(&account).is_some() {
+                return Err("Account is a holder key of another identity");
+            }
+            if self.locked_accounts.contains(&account) {
+                return Err("Account locked after too many failed attempts");
+            }
+            if self.denylist.contains(&DenylistEntry::Account(account)) {
+                return Err("Account is denylisted");
+            }
+            if self.disputes.get(&account).is_some() {
+                return Err("Dispute pending");
+            }
+            if !self.credential_types.get(&credential_type).map_or(false, |credential| credential.active) {
+                return Err("Credential type not active");
+            }
+            self.validate_attributes(credential_type, &name, age, &document_id, &proof_hash)?;
+            match document_expires_at {
+                Some(expires_at) if expires_at <= self.env().block_timestamp() => return Err("Document has expired"),
+                None if self.document_rules_of(document_kind).requires_expiry => {
+                    return Err("Document expiry date is required");
+                }
+                _ => {}
+            }
+            // Ensure identity does not already exist for this account (rejected or expired identities may resubmit)
+            let mut attempt_count = 1;
+            let mut failed_attempts = 0;
+            // Purged accounts keep their history, and with it their place in the registry order
+            let is_new = self.identities.get(&account).is_none() && !self.history_counts.contains_key(&account);
+            if let Some(existing) = self.identities.get(&account) {
+                if !matches!(existing.status, Status::Rejected | Status::Expired | Status::Withdrawn) {
+                    return Err("Identity already submitted");
+                }
+                attempt_count = existing.attempt_count.saturating_add(1);
+                failed_attempts = existing.failed_attempts;
+                let previous_hash = existing.document_hash;
+                if self.document_index.get(&previous_hash) == Some(&account) {
+                    self.document_index.take(&previous_hash);
+                }
             }
 
             // Create and store the identity
+            let document_hash = self.env().hash_bytes::<ink_env::hash::Blake2x256>(trim_fixed_bytes(&document_id));
+            if self.denylist.contains(&DenylistEntry::Document(document_hash)) {
+                return Err("Document is denylisted");
+            }
+            // A full queue only admits paid submissions that also cover the priority fee
+            let expedited = self.config.max_pending > 0 && self.pending.len() >= self.config.max_pending;
+            let expedite_fee = self.fees.fee(FeeKind::Expedite);
+            if expedited && (expedite_fee == 0 || fee_paid < self.current_submission_fee() + expedite_fee) {
+                return Err("Pending queue is full");
+            }
+            self.enforce_rate_limits(account)?;
+            let (fee_paid, priority_fee) = if expedited {
+                (fee_paid - expedite_fee, expedite_fee)
+            } else {
+                (fee_paid, 0)
+            };
             let identity = Identity {
-                name: name.clone(),
+                name,
                 age,
                 document_id,
-                proof_hash,
-                is_verified: false,
+                document_hash,
+                proofs: Vec::from([ProofEntry {
+                    kind: ProofKind::Document,
+                    algo: ProofAlgorithm::Blake2x256,
+                    digest: proof_hash,
+                    added_at: self.env().block_timestamp(),
+                    attested_by: None,
+                }]),
+                status: Status::Pending,
                 verifier: None,
+                fee_paid,
+                deposit,
+                relayer,
+                verified_at: 0,
+                expires_at: None,
+                credential_type,
+                attempt_count,
+                reject_reason: None,
+                failed_attempts,
+                assigned_verifier: None,
+                assignment_deadline: None,
+                assigned_at: None,
+                submitted_at: self.env().block_timestamp(),
+                review_time: 0,
+                notes_hash: None,
+                priority_fee,
+                certificate_id: None,
+                erased_at: None,
+                terms_version,
+                terms_accepted_at: self.env().block_timestamp(),
+                jurisdiction,
+                status_changed_at: self.env().block_timestamp(),
+                document_kind,
+                document_expires_at,
             };
-            self.identities.insert(caller, identity);
+            self.identities.insert(account, identity);
+            self.document_index.insert(document_hash, account);
+            if is_new {
+                self.accounts.push(account);
+            }
+            self.clear_sign_offs(account);
+            if self.config.auto_assign {
+                if let Some(verifier) = self.least_loaded_verifier(jurisdiction, document_kind, None) {
+                    let contract = self.env().account_id();
+                    self.set_assigned_verifier(account, contract, Some(verifier));
+                }
+            }
+            let mut stats = self.credential_stats.get(&credential_type).copied().unwrap_or_default();
+            stats.submitted += 1;
+            self.credential_stats.insert(credential_type, stats);
+            self.record_history(account, Status::Pending, relayer.unwrap_or(account), "Submitted");
+            if terms_version > 0 {
+                self.emit_consent_receipt(account, None, CONSENT_PURPOSE_VERIFICATION, DATA_CATEGORY_ALL);
+            }
+            self.enqueue_pending(account, expedited);
+            if expedited {
+                self.env().emit_event(IdentityExpedited {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    paid_by: self.env().caller(),
+                    amount: priority_fee,
+                });
+            }
 
             // Emit an event for identity submission
+            let document_topic = self.document_topic(document_hash);
             self.env().emit_event(IdentitySubmitted {
-                account: caller,
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
                 name,
                 age,
                 proof_hash,
+                document_topic,
             });
 
             Ok(())
         }
+    }
 
-        /// Verify an identity with a matching proof hash (only verifiers can call this)
-        #[ink(message)]
-        pub fn verify_identity(&mut self, account: AccountId, proof_hash: [u8; 32]) -> Result<(), &'static str> {
-            let caller = self.env().caller();
-            // Ensure the caller is a registered verifier
-            if !self.verifiers.contains(&caller) {
-                return Err("Only verifiers can verify identities");
+    // Each built-in component implementation is behind its cargo feature, so a fork can disable one and supply its own
+
+    /// Caller authorization, pause state and the admin audit trail
+    trait AccessControl {
+        /// Check if a caller is trusted with detail-level data (owner, verifiers or allowlisted relying parties)
+        fn is_trusted_caller(&self, caller: AccountId) -> bool;
+
+        /// Ensure the caller is the holder or an operator holding `permission` for the holder
+        fn ensure_holder_or_operator(&self, holder: AccountId, caller: AccountId, permission: u32) -> Result<(), &'static str>;
+
+        /// Identity a caller acts for as holder: the identity whose holder key it is, or else the caller's own account unless it was revoked
+        fn holder_of(&self, caller: AccountId) -> Result<AccountId, &'static str>;
+
+        /// Ensure the contract is not paused
+        fn ensure_not_paused(&self) -> Result<(), &'static str>;
+
+        /// Ensure the contract is not paused and the circuit breaker of a `MESSAGE_*` group is not tripped
+        fn ensure_enabled(&self, message: u32) -> Result<(), &'static str>;
+
+        /// Ensure the submitting or paying caller is allowed by the caller-origin policy
+        fn ensure_origin_allowed(&self, caller: AccountId) -> Result<(), &'static str>;
+
+        /// Append an admin action to the audit log, dropping the oldest entry once the log is full
+        fn record_admin_action(&mut self, actor: AccountId, action: AdminAction);
+    }
+
+    #[cfg(feature = "access-control")]
+    impl AccessControl for DIDVerifier {
+        fn is_trusted_caller(&self, caller: AccountId) -> bool {
+            caller == self.owner || self.verifiers.contains(&caller) || self.relying_parties.contains(&caller)
+        }
+
+        fn ensure_holder_or_operator(&self, holder: AccountId, caller: AccountId, permission: u32) -> Result<(), &'static str> {
+            if self.holder_of(caller) == Ok(holder) {
+                return Ok(());
+            }
+            match self.get_operator(holder, caller) {
+                Some(approval) if approval.permissions & permission == permission => Ok(()),
+                _ => Err("Caller is not the holder or an authorized operator"),
             }
+        }
 
-            // Ensure the identity exists and is not already verified
-            let identity = self.identities.get_mut(&account).ok_or("Identity not found")?;
-            if identity.is_verified {
-                return Err("Identity already verified");
+        fn holder_of(&self, caller: AccountId) -> Result<AccountId, &'static str> {
+            if let Some(identity) = self.key_identity.get(&caller) {
+                return Ok(*identity);
             }
+            if self.primary_key_revoked(caller) {
+                return Err("Key has been revoked");
+            }
+            Ok(caller)
+        }
 
-            // Ensure the proof hash matches the stored one
-            if identity.proof_hash != proof_hash {
-                return Err("Proof hash does not match");
+        fn ensure_not_paused(&self) -> Result<(), &'static str> {
+            if self.paused {
+                return Err("Contract is paused");
             }
+            Ok(())
+        }
 
-            // Mark the identity as verified
-            identity.is_verified = true;
-            identity.verifier = Some(caller);
+        fn ensure_enabled(&self, message: u32) -> Result<(), &'static str> {
+            self.ensure_not_paused()?;
+            if self.disabled_messages & message != 0 {
+                return Err("Message is disabled");
+            }
+            Ok(())
+        }
 
-            // Emit an event for identity verification
-            self.env().emit_event(IdentityVerified {
+        fn ensure_origin_allowed(&self, caller: AccountId) -> Result<(), &'static str> {
+            if self.config.block_contract_submitters && self.env().is_contract(&caller) {
+                return Err("Contracts may not submit identities");
+            }
+            Ok(())
+        }
+
+        fn record_admin_action(&mut self, actor: AccountId, action: AdminAction) {
+            let index = self.audit_count;
+            if index >= MAX_AUDIT_LOG {
+                self.audit_log.take(&(index - MAX_AUDIT_LOG));
+            }
+            let entry = AuditEntry {
+                index,
+                action,
+                actor,
+                block_number: self.env().block_number(),
+            };
+            self.audit_log.insert(index, entry);
+            self.audit_count += 1;
+        }
+    }
+
+    /// Verifier routing and review workload
+    trait VerifierRegistry {
+        /// Find the registered verifier with the fewest open assignments, skipping `exclude`
+        fn least_loaded_verifier(&self, jurisdiction: [u8; 2], document_kind: DocumentKind, exclude: Option<AccountId>) -> Option<AccountId>;
+
+        /// Count an assignment opened (`true`) or closed (`false`) against a verifier's workload
+        fn adjust_workload(&mut self, verifier: AccountId, opened: bool);
+
+        /// Ensure no other verifier holds a live assignment for the identity (lapsed assignments no longer bind)
+        fn ensure_routed_to(&self, account: AccountId, verifier: AccountId) -> Result<(), &'static str>;
+    }
+
+    #[cfg(feature = "verifier-registry")]
+    impl VerifierRegistry for DIDVerifier {
+        fn least_loaded_verifier(&self, jurisdiction: [u8; 2], document_kind: DocumentKind, exclude: Option<AccountId>) -> Option<AccountId> {
+            self.verifiers
+                .iter()
+                .filter(|verifier| {
+                    Some(**verifier) != exclude
+                        && self.accreditations.contains(&(**verifier, jurisdiction))
+                        && self.is_specialized(**verifier, document_kind)
+                })
+                .min_by_key(|verifier| self.verifier_stats.get(verifier).map_or(0, |stats| stats.open_assignments))
+                .copied()
+        }
+
+        fn adjust_workload(&mut self, verifier: AccountId, opened: bool) {
+            let mut stats = self.verifier_stats.get(&verifier).copied().unwrap_or_default();
+            stats.open_assignments = if opened {
+                stats.open_assignments + 1
+            } else {
+                stats.open_assignments.saturating_sub(1)
+            };
+            self.verifier_stats.insert(verifier, stats);
+        }
+
+        fn ensure_routed_to(&self, account: AccountId, verifier: AccountId) -> Result<(), &'static str> {
+            if let Some(identity) = self.identities.get(&account) {
+                if let Some(assigned) = identity.assigned_verifier {
+                    if assigned != verifier && !self.assignment_lapsed(identity) {
+                        return Err("Identity is assigned to another verifier");
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Identity records, their review queue and status history
+    trait IdentityStore {
+        /// Append a status transition to an identity's history
+        fn record_history(&mut self, account: AccountId, status: Status, actor: AccountId, reason: &str);
+
+        /// Append a note to an identity's history without touching its status timestamp, status word or the ledger
+        fn record_history_note(&mut self, account: AccountId, status: Status, actor: AccountId, reason: &str);
+
+        /// Append an account newly verified or revoked to the verification ledger
+        fn record_ledger_entry(&mut self, account: AccountId, status: Status);
+
+        /// Store a record brought over from another deployment and index it like a local one
+        fn insert_imported(&mut self, account: AccountId, identity: Identity);
+
+        /// Add an account to the pending index (and the priority lane when `priority`), announcing it with `WorkAvailable`
+        fn enqueue_pending(&mut self, account: AccountId, priority: bool);
+
+        /// Remove an account from the pending index (and the priority lane, if it was expedited)
+        fn remove_pending(&mut self, account: AccountId);
+
+        /// Raw storage cell holding `account`'s entry of the `identities` map
+        fn identity_cell(&self, account: &AccountId) -> Key;
+    }
+
+    #[cfg(feature = "identity-store")]
+    impl IdentityStore for DIDVerifier {
+        fn record_history(&mut self, account: AccountId, status: Status, actor: AccountId, reason: &str) {
+            self.record_history_note(account, status, actor, reason);
+            let now = self.env().block_timestamp();
+            match self.identities.get_mut(&account) {
+                Some(identity) => {
+                    identity.status_changed_at = now;
+                    let word = StatusWord {
+                        status: identity.status,
+                        expires_at: identity.lapses_at().unwrap_or(0),
+                    };
+                    self.status_words.insert(account, word);
+                }
+                None => {
+                    self.status_words.take(&account);
+                }
+            }
+        }
+
+        fn record_history_note(&mut self, account: AccountId, status: Status, actor: AccountId, reason: &str) {
+            let index = self.history_counts.get(&account).copied().unwrap_or(0);
+            let entry = HistoryEntry {
+                status,
+                actor,
+                block_number: self.env().block_number(),
+                reason: String::from(reason),
+            };
+            self.history.insert((account, index), entry);
+            self.history_counts.insert(account, index + 1);
+        }
+
+        fn record_ledger_entry(&mut self, account: AccountId, status: Status) {
+            let entry = LedgerEntry {
+                epoch: self.env().block_number() / EPOCH_LENGTH,
                 account,
-                verifier: caller,
+                status,
+            };
+            self.ledger.insert(self.ledger_len, entry);
+            self.ledger_len += 1;
+        }
+
+        fn insert_imported(&mut self, account: AccountId, identity: Identity) {
+            let status = identity.status;
+            let word = StatusWord {
+                status,
+                expires_at: identity.lapses_at().unwrap_or(0),
+            };
+            self.status_words.insert(account, word);
+            self.document_index.insert(identity.document_hash, account);
+            self.identities.insert(account, identity);
+            self.accounts.push(account);
+            match status {
+                Status::Pending | Status::PendingReverification => self.enqueue_pending(account, false),
+                Status::Verified => self.mint_badge(account),
+                _ => {}
+            }
+        }
+
+        fn identity_cell(&self, account: &AccountId) -> Key {
+            // The map's values follow its key stash, and each entry is keyed by hashing the pair as `HashMap` does
+            let mut ptr = KeyPtr::from(Key::from(IDENTITIES_ROOT_KEY));
+            ptr.advance_by(<ink_storage::collections::Stash<AccountId> as SpreadLayout>::FOOTPRINT);
+            let values_key = *ptr.advance_by(1);
+            let mut output = [0u8; 32];
+            ink_env::hash_encoded::<ink_env::hash::Blake2x256, _>(&(*b"ink hashmap", values_key, *account), &mut output);
+            Key::from(output)
+        }
+
+        fn enqueue_pending(&mut self, account: AccountId, priority: bool) {
+            let position = match self.pending_positions.get(&account).copied() {
+                Some(position) => position,
+                None => {
+                    let position = self.pending.len();
+                    self.pending_positions.insert(account, position);
+                    self.pending.push(account);
+                    position
+                }
+            };
+            let mut priority_position = self.priority_positions.get(&account).copied();
+            if priority && priority_position.is_none() {
+                let position = self.priority_pending.len();
+                self.priority_positions.insert(account, position);
+                self.priority_pending.push(account);
+                priority_position = Some(position);
+            }
+            self.env().emit_event(WorkAvailable {
+                schema_version: EVENT_SCHEMA_VERSION,
+                account,
+                position,
+                priority_position,
             });
+        }
 
-            Ok(())
+        fn remove_pending(&mut self, account: AccountId) {
+            self.remove_priority(account);
+            if let Some(index) = self.pending_positions.take(&account) {
+                let last = self.pending.len() - 1;
+                if index != last {
+                    self.pending.swap(index, last);
+                    if let Some(moved) = self.pending.get(index).copied() {
+                        self.pending_positions.insert(moved, index);
+                    }
+                }
+                self.pending.pop();
+            }
         }
+    }
 
-        /// Add a new verifier (only contract owner can add verifiers)
-        #[ink(message)]
-        pub fn add_verifier(&mut self, verifier: AccountId) -> Result<(), &'static str> {
-            let caller = self.env().caller();
-            // Ensure only the owner can add verifiers
-            if caller != self.owner {
-                return Err("Only the owner can add verifiers");
+    /// Collection and distribution of submission fees
+    trait Fees {
+        /// Check that the transferred value covers the current submission fee and deposit, returning both parts
+        fn collect_submission_payment(&self) -> Result<(Balance, Balance), &'static str>;
+
+        /// Check the transferred value covers `fee` plus the deposit, returning the held fee and deposit
+        fn collect_payment(&self, fee: Balance) -> Result<(Balance, Balance), &'static str>;
+
+        /// Split a resolved submission's fee between the verifier and the treasury
+        fn split_fee(&mut self, account: AccountId, verifier: AccountId, fee_paid: Balance);
+
+        /// Credit the priority fee of an expedited submission to the verifier that handled it
+        fn pay_priority_fee(&mut self, verifier: AccountId, priority_fee: Balance);
+
+        /// Ensure the contract can transfer out `amount` while staying above the existential deposit
+        fn ensure_can_pay(&self, amount: Balance) -> Result<(), &'static str>;
+    }
+
+    #[cfg(feature = "fees")]
+    impl Fees for DIDVerifier {
+        fn collect_submission_payment(&self) -> Result<(Balance, Balance), &'static str> {
+            self.collect_payment(self.current_submission_fee())
+        }
+
+        fn collect_payment(&self, fee: Balance) -> Result<(Balance, Balance), &'static str> {
+            let transferred = self.env().transferred_value();
+            if transferred < fee + self.config.submission_deposit {
+                return Err("Insufficient submission fee");
             }
+            let deposit = self.config.submission_deposit;
+            Ok((transferred - deposit, deposit))
+        }
 
-            // Add the verifier to the set of verifiers
-            self.verifiers.insert(verifier);
-            Ok(())
+        fn split_fee(&mut self, account: AccountId, verifier: AccountId, fee_paid: Balance) {
+            let verifier_share = fee_paid * self.config.verifier_share_bps as Balance / BASIS_POINTS;
+            let treasury_share = fee_paid - verifier_share;
+            self.treasury.total_collected += treasury_share;
+            if verifier_share > 0 {
+                let earned = self.earnings.get(&verifier).copied().unwrap_or(0);
+                self.earnings.insert(verifier, earned + verifier_share);
+            }
+            if fee_paid > 0 {
+                self.env().emit_event(FeeSplit {
+                    schema_version: EVENT_SCHEMA_VERSION,
+                    account,
+                    verifier,
+                    verifier_share,
+                    treasury_share,
+                });
+            }
         }
 
-        /// Remove a verifier (only contract owner can remove verifiers)
-        #[ink(message)]
-        pub fn remove_verifier(&mut self, verifier: AccountId) -> Result<(), &'static str> {
-            let caller = self.env().caller();
-            // Ensure only the owner can remove verifiers
-            if caller != self.owner {
-                return Err("Only the owner can remove verifiers");
+        fn pay_priority_fee(&mut self, verifier: AccountId, priority_fee: Balance) {
+            if priority_fee > 0 {
+                let earned = self.earnings.get(&verifier).copied().unwrap_or(0);
+                self.earnings.insert(verifier, earned + priority_fee);
             }
+        }
 
-            // Remove the verifier from the set of verifiers
-            self.verifiers.take(&verifier);
+        fn ensure_can_pay(&self, amount: Balance) -> Result<(), &'static str> {
+            if amount > 0 && self.env().balance() < amount.saturating_add(self.env().minimum_balance()) {
+                return Err("Insufficient contract balance");
+            }
             Ok(())
         }
+    }
 
+    impl DidRegistry for DIDVerifier {
         /// Check if an identity is verified
         #[ink(message)]
-        pub fn is_verified(&self, account: AccountId) -> bool {
-            if let Some(identity) = self.identities.get(&account) {
-                return identity.is_verified;
-            }
-            false
+        fn is_verified(&self, account: AccountId) -> bool {
+            self.get_effective_status(account) == Some(Status::Verified)
         }
 
-        /// Get the stored identity for a specific account
+        /// Get the verification record for an account (full detail for trusted callers and subscribers)
         #[ink(message)]
-        pub fn get_identity(&self, account: AccountId) -> Option<Identity> {
-            self.identities.get(&account).cloned()
+        fn get_verification_record(&self, account: AccountId) -> Option<VerificationRecord> {
+            let identity = self.identities.get(&account)?;
+            let caller = self.env().caller();
+            let privileged = self.is_trusted_caller(caller) || self.is_subscribed(caller);
+            Some(VerificationRecord {
+                status: self.effective_status(identity),
+                verifier: if privileged { identity.verifier } else { None },
+                redacted: !privileged,
+            })
         }
+    }
 
-        /// Check if an account is a registered verifier
-        #[ink(message)]
-        pub fn is_verifier(&self, account: AccountId) -> bool {
-            self.verifiers.contains(&account)
+    /// Zero-pad `value` into a fixed-size field such as `Name` or `DocumentId`, or `None` if it does not fit
+    pub fn to_fixed_bytes<const N: usize>(value: &str) -> Option<[u8; N]> {
+        let bytes = value.as_bytes();
+        if bytes.len() > N {
+            return None;
+        }
+        let mut field = [0u8; N];
+        field[..bytes.len()].copy_from_slice(bytes);
+        Some(field)
+    }
+
+    /// Zero-pad `value` into a fixed-size field, cutting it at the last character boundary that fits
+    fn to_fixed_bytes_lossy<const N: usize>(value: &str) -> [u8; N] {
+        let mut len = value.len().min(N);
+        while !value.is_char_boundary(len) {
+            len -= 1;
+        }
+        to_fixed_bytes(&value[..len]).unwrap_or([0; N])
+    }
+
+    /// Re-encode a raw `identities` entry written by an earlier storage version, or `None` if it is already current
+    fn upgrade_identity_entry(bytes: &[u8]) -> Result<Option<(Identity, u32)>, &'static str> {
+        use scale::DecodeAll;
+        // Map entries are stored with the index of their key in the map's key stash
+        if <(Identity, u32)>::decode_all(&mut &bytes[..]).is_ok() {
+            return Ok(None);
+        }
+        if let Ok((old, document_kind, document_expires_at, key_index)) =
+            <(IdentityLayout2, DocumentKind, Option<Timestamp>, u32)>::decode_all(&mut &bytes[..])
+        {
+            return Ok(Some((old.into_identity(document_kind, document_expires_at), key_index)));
+        }
+        if let Ok((old, key_index)) = <(IdentityLayout2, u32)>::decode_all(&mut &bytes[..]) {
+            return Ok(Some((old.into_identity(DocumentKind::Other, None), key_index)));
+        }
+        if let Ok((old, key_index)) = <(IdentityLayout1, u32)>::decode_all(&mut &bytes[..]) {
+            return Ok(Some((Identity::from(old), key_index)));
+        }
+        Err("Unrecognized identity record")
+    }
+
+    /// The bytes of a fixed-size field without its zero padding
+    pub fn trim_fixed_bytes(field: &[u8]) -> &[u8] {
+        let len = field.iter().rposition(|byte| *byte != 0).map_or(0, |last| last + 1);
+        &field[..len]
+    }
+
+    /// Decode a fixed-size field back into a string, or `None` if it is not valid UTF-8
+    pub fn from_fixed_bytes(field: &[u8]) -> Option<String> {
+        core::str::from_utf8(trim_fixed_bytes(field)).ok().map(String::from)
+    }
+
+    /// ABI-encode an unsigned integer as a big-endian 32-byte word
+    fn abi_uint(value: impl Into<u128>) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[16..].copy_from_slice(&value.into().to_be_bytes());
+        word
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        /// Act as a malicious callee (fee token, refund recipient or hook) calling back while the contract's outbound call is in flight
+        fn reenter_as(contract: &DIDVerifier, callee: AccountId) {
+            contract.set_entered(true);
+            ink_env::test::set_caller::<Environment>(callee);
+        }
+
+        /// A pending identity with every optional detail filled in, as a submission would store it
+        fn sample_identity() -> Identity {
+            let mut identity = Identity {
+                name: to_fixed_bytes("Alice Example").unwrap(),
+                age: 30,
+                document_id: to_fixed_bytes("P1234567").unwrap(),
+                document_hash: [1; 32],
+                proofs: Vec::new(),
+                status: Status::Pending,
+                verifier: None,
+                fee_paid: 1_000,
+                deposit: 1_000,
+                relayer: None,
+                verified_at: 0,
+                expires_at: None,
+                credential_type: DEFAULT_CREDENTIAL_TYPE,
+                attempt_count: 1,
+                reject_reason: None,
+                failed_attempts: 0,
+                assigned_verifier: None,
+                assignment_deadline: None,
+                assigned_at: None,
+                submitted_at: 1,
+                review_time: 0,
+                priority_fee: 0,
+                notes_hash: None,
+                certificate_id: None,
+                erased_at: None,
+                terms_version: 0,
+                terms_accepted_at: 0,
+                jurisdiction: *b"DE",
+                status_changed_at: 1,
+                document_kind: DocumentKind::Passport,
+                document_expires_at: Some(1_000_000),
+            };
+            identity.set_proof_hash([2; 32], 1);
+            identity
+        }
+
+        #[ink::test]
+        fn status_word_is_cheaper_to_read_than_the_identity() {
+            // Storage reads are weighed by the bytes loaded, so compare what `is_verified` decodes against the full record
+            let identity = sample_identity();
+            let word = StatusWord {
+                status: identity.status,
+                expires_at: identity.lapses_at().unwrap_or(0),
+            };
+            let word_size = scale::Encode::encoded_size(&word);
+            let identity_size = scale::Encode::encoded_size(&identity);
+            assert_eq!(word_size, 9);
+            assert!(identity_size > 20 * word_size, "identity encodes to {} bytes", identity_size);
+        }
+
+        #[ink::test]
+        fn status_word_mirrors_the_stored_identity() {
+            let accounts = ink_env::test::default_accounts::<Environment>();
+            let mut contract = DIDVerifier::new();
+            contract.insert_imported(accounts.bob, sample_identity());
+
+            assert_eq!(contract.status_words.get(&accounts.bob).map(|word| word.expires_at), Some(1_000_000));
+            assert_eq!(contract.get_effective_status(accounts.bob), Some(Status::Pending));
+            assert!(!contract.is_verified(accounts.bob));
+        }
+
+        #[ink::test]
+        fn reentrant_value_paths_are_refused() {
+            let accounts = ink_env::test::default_accounts::<Environment>();
+            let mut contract = DIDVerifier::new();
+            reenter_as(&contract, accounts.eve);
+
+            assert_eq!(contract.withdraw(1, accounts.eve), Err("Reentrant call"));
+            assert_eq!(contract.withdraw_token(1, accounts.eve), Err("Reentrant call"));
+            assert_eq!(contract.claim_earnings(), Err("Reentrant call"));
+            assert_eq!(contract.cancel_request(), Err("Reentrant call"));
+            assert_eq!(contract.reclaim_bounty(accounts.bob), Err("Reentrant call"));
+            assert_eq!(contract.resolve_dispute(accounts.bob, true), Err("Reentrant call"));
+            assert_eq!(contract.poke_expiry(Vec::new()), Err("Reentrant call"));
+            assert_eq!(contract.migrate_records(Vec::new()), Err("Reentrant call"));
+        }
+
+        #[ink::test]
+        fn reentrant_signed_submissions_are_refused() {
+            let accounts = ink_env::test::default_accounts::<Environment>();
+            let mut contract = DIDVerifier::new();
+            reenter_as(&contract, accounts.eve);
+
+            let permit = Permit { value: 0, deadline: 0, signature: [0; 65] };
+            assert_eq!(contract.submit_identity_with_permit(Vec::new(), [0; 65], permit), Err("Reentrant call"));
+            assert_eq!(contract.relay_batch(Vec::new()), Err("Reentrant call"));
+        }
+
+        #[ink::test]
+        fn guard_is_released_once_the_outbound_call_returns() {
+            let accounts = ink_env::test::default_accounts::<Environment>();
+            let mut contract = DIDVerifier::new();
+            reenter_as(&contract, accounts.eve);
+            assert_eq!(contract.claim_earnings(), Err("Reentrant call"));
+
+            contract.set_entered(false);
+            assert_eq!(contract.claim_earnings(), Err("No earnings to claim"));
+
+            // Hook notification takes and releases the guard around its calls
+            contract.notify_hooks(accounts.bob, Status::Verified);
+            assert_eq!(contract.ensure_not_entered(), Ok(()));
+        }
+
+        /// Assert that every value-moving message, batches included, refuses a caller re-entering mid-call
+        fn assert_value_paths_refused(contract: &mut DIDVerifier, callee: AccountId) {
+            ink_env::test::set_caller::<Environment>(callee);
+            assert_eq!(contract.verify_identities(Vec::new()), Err("Reentrant call"));
+            assert_eq!(contract.reject_identities(Vec::new()), Err("Reentrant call"));
+            assert_eq!(contract.relay_batch(Vec::new()), Err("Reentrant call"));
+            assert_eq!(contract.poke_expiry(Vec::new()), Err("Reentrant call"));
+            assert_eq!(contract.migrate_records(Vec::new()), Err("Reentrant call"));
+            assert_eq!(contract.claim_earnings(), Err("Reentrant call"));
+            assert_eq!(contract.withdraw(1, callee), Err("Reentrant call"));
+        }
+
+        #[ink::test]
+        fn hook_notification_in_flight_holds_the_guard() {
+            let accounts = ink_env::test::default_accounts::<Environment>();
+            let mut contract = DIDVerifier::new();
+            assert_eq!(contract.add_hook(accounts.django), Ok(()));
+            let mut identity = sample_identity();
+            identity.status = Status::Verified;
+            identity.verifier = Some(accounts.bob);
+            contract.insert_imported(accounts.charlie, identity);
+
+            // The off-chain engine cannot run the hook, so revocation stops inside the hook call,
+            // at the point where a malicious hook would call back
+            let revoked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                contract.revoke_identity(accounts.charlie)
+            }));
+            assert!(revoked.is_err());
+            assert_eq!(contract.ensure_not_entered(), Err("Reentrant call"));
+            assert_value_paths_refused(&mut contract, accounts.django);
+        }
+
+        #[ink::test]
+        fn token_pull_in_flight_holds_the_guard() {
+            let accounts = ink_env::test::default_accounts::<Environment>();
+            let mut contract = DIDVerifier::new();
+            let permit = Permit { value: 1, deadline: 0, signature: [0; 65] };
+
+            // As above, the pull stops inside the call to the fee token
+            let pulled = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                contract.pull_token_fee(accounts.frank, accounts.bob, 1, &permit)
+            }));
+            assert!(pulled.is_err());
+            assert_eq!(contract.ensure_not_entered(), Err("Reentrant call"));
+            assert_value_paths_refused(&mut contract, accounts.frank);
         }
     }
 }
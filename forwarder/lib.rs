@@ -0,0 +1,66 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+/// Minimal forwarder giving a `DIDVerifier` deployment an address that survives code rewrites
+///
+/// Every call is delegate-called into the logic code, so the logic runs on the forwarder's own storage
+/// and sees the original caller and transferred value. The forwarder keeps no `#[ink(storage)]` fields:
+/// its only state is the logic code hash, stored at a raw key the logic contract reserves, and that hash
+/// is replaced by the logic contract's own timelocked `apply_upgrade()`.
+#[ink::contract]
+mod forwarder {
+    use ink_env::call::{build_call, DelegateCall};
+    use ink_env::CallFlags;
+    use ink_primitives::Key;
+
+    /// Raw storage key of the logic code hash, reserved by `DIDVerifier` as `FORWARDER_LOGIC_KEY`
+    const FORWARDER_LOGIC_KEY: [u8; 32] = *b"didv::forwarder_logic___________";
+
+    /// A storage cell value written verbatim, without a length prefix
+    struct RawCell(Vec<u8>);
+
+    impl scale::Encode for RawCell {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(&self.0);
+        }
+    }
+
+    #[ink(storage)]
+    pub struct Forwarder {}
+
+    impl Forwarder {
+        /// Constructor pinning the logic code and seeding the logic contract's storage root
+        ///
+        /// Constructors cannot be delegate-called, so `cells` are the raw storage cells written by a `DIDVerifier`
+        /// constructor (e.g. `new_with_owner`) on a template deployment, read off-chain and copied here.
+        #[ink(constructor)]
+        pub fn new(logic_code_hash: [u8; 32], cells: Vec<([u8; 32], Vec<u8>)>) -> Self {
+            for (key, value) in cells {
+                assert!(key != FORWARDER_LOGIC_KEY, "Reserved storage key");
+                ink_env::set_contract_storage(&Key::from(key), &RawCell(value));
+            }
+            ink_env::set_contract_storage(&Key::from(FORWARDER_LOGIC_KEY), &logic_code_hash);
+            Self {}
+        }
+
+        /// Delegate every message to the logic code, forwarding the input and returning its output unchanged
+        #[ink(message, payable, selector = _)]
+        pub fn forward(&self) {
+            let logic = ink_env::get_contract_storage::<[u8; 32]>(&Key::from(FORWARDER_LOGIC_KEY))
+                .ok()
+                .flatten()
+                .expect("Logic code hash not set");
+            build_call::<Environment>()
+                .call_type(DelegateCall::new().code_hash(Hash::from(logic)))
+                .call_flags(CallFlags::default().set_forward_input(true).set_tail_call(true))
+                .fire()
+                .unwrap_or_else(|err| panic!("Delegate call to logic code failed: {:?}", err));
+            unreachable!("the tail call returns the logic code's output directly");
+        }
+    }
+}